@@ -0,0 +1,48 @@
+//! Compares `iter_write` and `copy_write` for frames of `Copy` events.
+//!
+//! Run with `cargo bench --bench copy_write`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use shrev::EventChannel;
+
+const EVENTS: usize = 10_000;
+
+type Event = [f32; 4];
+
+/// Writes a frame of `EVENTS` events with `write` and reads them all, like a
+/// game loop would once per frame.
+fn frame<F>(c: &mut Criterion, write: &[(&str, F)])
+where
+    F: Fn(&mut EventChannel<Event>, &[Event]),
+{
+    let events = vec![[1.0; 4]; EVENTS];
+    let mut group = c.benchmark_group("write_frame");
+    group.throughput(Throughput::Elements(EVENTS as u64));
+
+    for (name, write) in write {
+        let mut channel = EventChannel::with_capacity(EVENTS);
+        let mut reader = channel.register_reader();
+
+        group.bench_with_input(BenchmarkId::new(*name, EVENTS), &events, |b, events| {
+            b.iter(|| {
+                write(&mut channel, events);
+                channel.read(&mut reader).len()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn write_frame(c: &mut Criterion) {
+    let iter_write: fn(&mut EventChannel<Event>, &[Event]) =
+        |channel, events| channel.iter_write(events.iter().copied());
+    let copy_write: fn(&mut EventChannel<Event>, &[Event]) =
+        |channel, events| channel.copy_write(events);
+
+    frame(c, &[("iter_write", iter_write), ("copy_write", copy_write)]);
+}
+
+criterion_group!(benches, write_frame);
+criterion_main!(benches);
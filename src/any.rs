@@ -0,0 +1,213 @@
+//! Type erasure for event channels.
+
+use std::any::{Any, TypeId};
+
+use crate::{Event, EventChannel, Stats};
+
+/// An `EventChannel` of any event type.
+///
+/// This allows keeping channels of different event types in one collection,
+/// e.g. a map from the `TypeId` of the events to the channel. The concrete
+/// channel can be retrieved with `downcast_ref` and `downcast_mut`, while
+/// maintenance which doesn't depend on the event type, like `maintain` or
+/// `resize`, can be done through the trait.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::{AnyChannel, EventChannel};
+///
+/// let mut channels: Vec<Box<dyn AnyChannel>> = vec![
+///     Box::new(EventChannel::<u32>::new()),
+///     Box::new(EventChannel::<String>::new()),
+/// ];
+///
+/// assert!(channels[0].downcast_ref::<u32>().is_some());
+/// assert!(channels[0].downcast_ref::<String>().is_none());
+///
+/// for channel in &mut channels {
+///     channel.maintain();
+///     channel.shrink_to_fit();
+/// }
+/// ```
+pub trait AnyChannel: Any {
+    /// Returns `self` as `&dyn Any`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns `self` as `&mut dyn Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns the `TypeId` of the events of the channel.
+    ///
+    /// Note that `type_id` returns the `TypeId` of the channel itself.
+    fn event_type_id(&self) -> TypeId;
+
+    /// Returns the number of events stored in the channel, see
+    /// `EventChannel::iter_all`.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the channel stores no events.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of events the channel can store without growing.
+    fn capacity(&self) -> usize;
+
+    /// See `EventChannel::maintain`.
+    fn maintain(&mut self);
+
+    /// See `EventChannel::stats`.
+    fn stats(&self) -> Stats;
+
+    /// See `EventChannel::reset_stats`.
+    fn reset_stats(&mut self);
+
+    /// See `EventChannel::resize`.
+    fn resize(&mut self, size: usize);
+
+    /// See `EventChannel::shrink_to_fit`.
+    fn shrink_to_fit(&mut self);
+}
+
+impl<E> AnyChannel for EventChannel<E>
+where
+    E: Event,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event_type_id(&self) -> TypeId {
+        TypeId::of::<E>()
+    }
+
+    fn len(&self) -> usize {
+        self.iter_all().len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    fn maintain(&mut self) {
+        EventChannel::maintain(self);
+    }
+
+    fn stats(&self) -> Stats {
+        EventChannel::stats(self)
+    }
+
+    fn reset_stats(&mut self) {
+        EventChannel::reset_stats(self);
+    }
+
+    fn resize(&mut self, size: usize) {
+        EventChannel::resize(self, size);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        EventChannel::shrink_to_fit(self);
+    }
+}
+
+macro_rules! impl_downcast {
+    ($ty:ty) => {
+        impl $ty {
+            /// Returns the channel if its events are of type `E`.
+            pub fn downcast_ref<E: Event>(&self) -> Option<&EventChannel<E>> {
+                self.as_any().downcast_ref()
+            }
+
+            /// Returns the channel if its events are of type `E`.
+            pub fn downcast_mut<E: Event>(&mut self) -> Option<&mut EventChannel<E>> {
+                self.as_any_mut().downcast_mut()
+            }
+        }
+    };
+}
+
+impl_downcast!(dyn AnyChannel);
+impl_downcast!(dyn AnyChannel + Send + Sync);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_downcast() {
+        let mut channels: HashMap<TypeId, Box<dyn AnyChannel>> = HashMap::new();
+        channels.insert(TypeId::of::<u32>(), Box::new(EventChannel::<u32>::new()));
+        channels.insert(TypeId::of::<&str>(), Box::new(EventChannel::<&str>::new()));
+
+        let numbers = channels
+            .get_mut(&TypeId::of::<u32>())
+            .and_then(|c| c.downcast_mut::<u32>())
+            .unwrap();
+        let mut reader = numbers.register_reader();
+        numbers.iter_write(0..3);
+        assert_eq!(
+            numbers.read(&mut reader).cloned().collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        let strings = channels
+            .get_mut(&TypeId::of::<&str>())
+            .and_then(|c| c.downcast_mut::<&str>())
+            .unwrap();
+        let mut reader = strings.register_reader();
+        strings.single_write("event");
+        assert_eq!(
+            strings.read(&mut reader).cloned().collect::<Vec<_>>(),
+            vec!["event"]
+        );
+
+        assert!(channels[&TypeId::of::<u32>()]
+            .downcast_ref::<&str>()
+            .is_none());
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let mut numbers = EventChannel::<u32>::with_capacity(4);
+        let mut reader = numbers.register_reader();
+        numbers.iter_write(0..6);
+        let mut channels: Vec<Box<dyn AnyChannel>> =
+            vec![Box::new(numbers), Box::new(EventChannel::<&str>::with_capacity(4))];
+
+        assert_eq!(channels[0].len(), 6);
+        assert!(channels[1].is_empty());
+        assert_eq!(channels[0].stats().events_written, 6);
+        for channel in &mut channels {
+            channel.resize(16);
+            channel.reset_stats();
+        }
+        assert_eq!(channels.iter().map(|c| c.capacity()).collect::<Vec<_>>(), [16, 16]);
+        assert_eq!(channels[0].stats(), Stats::default());
+
+        let numbers = channels[0].downcast_mut::<u32>().unwrap();
+        assert_eq!(numbers.read(&mut reader).len(), 6);
+        drop(reader);
+        for channel in &mut channels {
+            channel.maintain();
+            channel.shrink_to_fit();
+        }
+        assert_eq!(channels.iter().map(|c| c.capacity()).collect::<Vec<_>>(), [2, 2]);
+        assert_eq!(channels[0].len(), 2);
+    }
+
+    #[test]
+    fn test_event_type_id() {
+        let channel: Box<dyn AnyChannel> = Box::new(EventChannel::<u32>::new());
+
+        assert_eq!(channel.event_type_id(), TypeId::of::<u32>());
+        assert_ne!(channel.event_type_id(), TypeId::of::<&str>());
+        assert_ne!(channel.event_type_id(), channel.as_any().type_id());
+    }
+}
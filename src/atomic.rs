@@ -0,0 +1,205 @@
+//! Bounded ring buffer that allows multiple threads to write concurrently.
+
+use std::{
+    cell::UnsafeCell,
+    fmt,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct Slot<T> {
+    /// Tells producers and the consumer whose turn it is to use this slot.
+    ///
+    /// A slot with `sequence == pos` is free for the producer claiming `pos`,
+    /// one with `sequence == pos + 1` holds the event written at `pos`.
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Fixed-size ring buffer with an atomic write cursor.
+///
+/// Unlike `EventChannel`, this buffer never grows; its memory is allocated
+/// once in `new`. Writing only requires a shared reference, so any number of
+/// producer threads can append events concurrently without a lock. Events
+/// are handed out in the order their writes claimed a slot, and every event
+/// is read exactly once.
+///
+/// A write to a full buffer fails and hands the event back instead of
+/// overwriting anything.
+///
+/// ## Examples
+///
+/// ```
+/// use std::{sync::Arc, thread};
+///
+/// use shrev::AtomicRingBuffer;
+///
+/// let buffer = Arc::new(AtomicRingBuffer::new(16));
+///
+/// let producer = {
+///     let buffer = buffer.clone();
+///     thread::spawn(move || buffer.single_write(5u32))
+/// };
+/// producer.join().unwrap().unwrap();
+///
+/// assert_eq!(buffer.read_single(), Some(5));
+/// assert_eq!(buffer.read_single(), None);
+/// ```
+pub struct AtomicRingBuffer<T> {
+    slots: Box<[Slot<T>]>,
+    mask: usize,
+    write_pos: AtomicUsize,
+    read_pos: AtomicUsize,
+}
+
+impl<T: Copy> AtomicRingBuffer<T> {
+    /// Create a new buffer that can hold at least `size` events.
+    ///
+    /// The size is rounded up to the next power of two, and to at least two,
+    /// since a single slot couldn't tell a full buffer from an empty one.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "`AtomicRingBuffer` needs a capacity");
+
+        let size = size.max(2).next_power_of_two();
+        let slots = (0..size)
+            .map(|i| Slot {
+                sequence: AtomicUsize::new(i),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+
+        AtomicRingBuffer {
+            slots,
+            mask: size - 1,
+            write_pos: AtomicUsize::new(0),
+            read_pos: AtomicUsize::new(0),
+        }
+    }
+
+    /// The number of events this buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Write a single event into the buffer.
+    ///
+    /// Returns the event back if the buffer is full.
+    pub fn single_write(&self, event: T) -> Result<(), T> {
+        let mut pos = self.write_pos.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.slots[pos & self.mask];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+
+            match sequence.wrapping_sub(pos) as isize {
+                0 => match self.write_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // We claimed `pos`, nobody else touches this slot until we
+                        // publish it.
+                        unsafe {
+                            (*slot.value.get()).as_mut_ptr().write(event);
+                        }
+                        slot.sequence.store(pos.wrapping_add(1), Ordering::Release);
+
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                },
+                // The slot still holds an event from the previous lap.
+                x if x < 0 => return Err(event),
+                // Another producer claimed `pos` already.
+                _ => pos = self.write_pos.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Read the oldest event which has not been read yet.
+    ///
+    /// Returns `None` if there is no such event.
+    pub fn read_single(&self) -> Option<T> {
+        let mut pos = self.read_pos.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.slots[pos & self.mask];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+
+            match sequence.wrapping_sub(pos.wrapping_add(1)) as isize {
+                0 => match self.read_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // The event was published with `Release` and is `Copy`,
+                        // so reading it out leaves nothing to drop.
+                        let event = unsafe { (*slot.value.get()).as_ptr().read() };
+                        slot.sequence
+                            .store(pos.wrapping_add(self.slots.len()), Ordering::Release);
+
+                        return Some(event);
+                    }
+                    Err(current) => pos = current,
+                },
+                // Nothing has been published at `pos` yet.
+                x if x < 0 => return None,
+                _ => pos = self.read_pos.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Returns an iterator reading all events that are currently available.
+    pub fn drain(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.read_single())
+    }
+}
+
+impl<T> fmt::Debug for AtomicRingBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicRingBuffer")
+            .field("capacity", &self.slots.len())
+            .field("write_pos", &self.write_pos)
+            .field("read_pos", &self.read_pos)
+            .finish()
+    }
+}
+
+// Slots are only ever accessed by the thread which claimed them through one of
+// the atomic cursors.
+unsafe impl<T: Send> Send for AtomicRingBuffer<T> {}
+unsafe impl<T: Send> Sync for AtomicRingBuffer<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds_up() {
+        let buffer = AtomicRingBuffer::<u8>::new(5);
+        assert_eq!(buffer.capacity(), 8);
+        let buffer = AtomicRingBuffer::<u8>::new(1);
+        assert_eq!(buffer.capacity(), 2);
+    }
+
+    #[test]
+    fn test_full() {
+        let buffer = AtomicRingBuffer::new(2);
+
+        assert_eq!(buffer.single_write(1), Ok(()));
+        assert_eq!(buffer.single_write(2), Ok(()));
+        assert_eq!(buffer.single_write(3), Err(3));
+
+        assert_eq!(buffer.read_single(), Some(1));
+        assert_eq!(buffer.single_write(3), Ok(()));
+        assert_eq!(buffer.drain().collect::<Vec<_>>(), vec![2, 3]);
+    }
+}
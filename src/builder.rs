@@ -0,0 +1,197 @@
+//! Configuring channels in one place.
+
+use std::fmt;
+
+use crate::{DropPolicy, Event, EventChannel, StampedChannel, DEFAULT_CAPACITY};
+
+/// The reason a `ChannelBuilder` couldn't build a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// The capacity is zero.
+    ZeroCapacity,
+    /// The capacity is one, which can't tell a full buffer from an empty one
+    /// once a reader is registered.
+    CapacityTooSmall,
+    /// The maximum age of readers is zero, so they would never read any
+    /// event.
+    ZeroMaxAge,
+    /// A `StampedChannel` is built paused, which would stamp all the events
+    /// queued while paused with the same sequence number.
+    PausedStamped,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BuildError::ZeroCapacity => "the capacity of a channel must not be zero",
+            BuildError::CapacityTooSmall => "the capacity of a channel must be at least two",
+            BuildError::ZeroMaxAge => "the maximum age of readers must not be zero",
+            BuildError::PausedStamped => "a stamped channel must not start out paused",
+        })
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Configures a channel before creating it, checking the configuration
+/// instead of panicking or misbehaving later.
+///
+/// Obtained from `ChannelBuilder::new` or `EventChannel::builder`.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::{BuildError, ChannelBuilder, DropPolicy, EventChannel};
+///
+/// let mut channel: EventChannel<u32> = ChannelBuilder::new()
+///     .capacity(16)
+///     .policy(DropPolicy::DropNewest)
+///     .reader_max_age(4)
+///     .build()
+///     .unwrap();
+///
+/// let mut reader = channel.register_reader();
+/// channel.iter_write(0..6);
+/// assert_eq!(channel.read(&mut reader).collect::<Vec<_>>(), vec![&2, &3, &4, &5]);
+///
+/// let error = ChannelBuilder::new().capacity(0).build::<u32>().unwrap_err();
+/// assert_eq!(error, BuildError::ZeroCapacity);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelBuilder {
+    capacity: usize,
+    policy: DropPolicy,
+    reader_max_age: Option<usize>,
+    paused: bool,
+}
+
+impl ChannelBuilder {
+    /// Create a new builder, with the same configuration as
+    /// `EventChannel::new`.
+    pub fn new() -> Self {
+        ChannelBuilder {
+            capacity: DEFAULT_CAPACITY,
+            policy: DropPolicy::default(),
+            reader_max_age: None,
+            paused: false,
+        }
+    }
+
+    /// Sets the starting capacity, which must be at least two.
+    pub fn capacity(mut self, size: usize) -> Self {
+        self.capacity = size;
+        self
+    }
+
+    /// Sets how writes which don't fit are handled, i.e. whether the
+    /// channel grows or drops events.
+    pub fn policy(mut self, policy: DropPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets how many of the most recent events readers read at most, so
+    /// older events expire for them.
+    ///
+    /// This applies to the readers registered with `register_reader`, like
+    /// `EventChannel::register_reader_max_age` does for a single reader.
+    pub fn reader_max_age(mut self, max_age: usize) -> Self {
+        self.reader_max_age = Some(max_age);
+        self
+    }
+
+    /// Sets whether writing starts out paused, see
+    /// `EventChannel::set_paused`.
+    pub fn paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Checks the configuration and creates an `EventChannel` with it.
+    pub fn build<E: Event>(self) -> Result<EventChannel<E>, BuildError> {
+        self.validate()?;
+
+        let mut channel = EventChannel::with_policy(self.capacity, self.policy);
+        if let Some(max_age) = self.reader_max_age {
+            channel.storage.set_reader_max_age(max_age);
+        }
+        channel.set_paused(self.paused);
+
+        Ok(channel)
+    }
+
+    /// Checks the configuration and creates a `StampedChannel` with it.
+    ///
+    /// The channel must not be paused, since events are stamped when they
+    /// are written, not when they are stored.
+    pub fn build_stamped<E: Event>(self) -> Result<StampedChannel<E>, BuildError> {
+        if self.paused {
+            return Err(BuildError::PausedStamped);
+        }
+
+        self.build().map(StampedChannel::from_channel)
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        match (self.capacity, self.reader_max_age) {
+            (0, _) => Err(BuildError::ZeroCapacity),
+            (1, _) => Err(BuildError::CapacityTooSmall),
+            (_, Some(0)) => Err(BuildError::ZeroMaxAge),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for ChannelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let build = |builder: ChannelBuilder| builder.build::<i32>().err();
+
+        assert_eq!(build(ChannelBuilder::new()), None);
+        assert_eq!(build(ChannelBuilder::new().capacity(0)), Some(BuildError::ZeroCapacity));
+        assert_eq!(build(ChannelBuilder::new().capacity(1)), Some(BuildError::CapacityTooSmall));
+        assert_eq!(build(ChannelBuilder::new().capacity(2)), None);
+        assert_eq!(build(ChannelBuilder::new().reader_max_age(0)), Some(BuildError::ZeroMaxAge));
+
+        let stamped = ChannelBuilder::new().paused(true).build_stamped::<i32>();
+        assert_eq!(stamped.err(), Some(BuildError::PausedStamped));
+        assert!(ChannelBuilder::new().paused(false).build_stamped::<i32>().is_ok());
+    }
+
+    #[test]
+    fn test_build() {
+        let mut channel = ChannelBuilder::new()
+            .capacity(2)
+            .policy(DropPolicy::DropNewest)
+            .paused(true)
+            .reader_max_age(3)
+            .build()
+            .unwrap();
+        let mut reader = channel.register_reader();
+        let mut all = channel.register_reader_max_age(usize::MAX);
+
+        assert!(channel.is_paused());
+        channel.iter_write(0..5);
+        channel.set_paused(false);
+        assert_eq!(channel.read(&mut reader).cloned().collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(channel.read(&mut all).cloned().collect::<Vec<_>>(), [0, 1]);
+
+        let mut channel = ChannelBuilder::new()
+            .reader_max_age(3)
+            .build_stamped()
+            .unwrap();
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..5);
+        let read: Vec<_> = channel.read(&mut reader).map(|s| s.event).collect();
+        assert_eq!(read, [2, 3, 4]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Channels of state changes.
+
+use crate::{Event, EventChannel, EventIterator, ReaderId};
+
+/// A channel of values which only stores a new value if it differs from the
+/// previous one.
+///
+/// This is meant for state where readers only need to know about
+/// transitions, like the window focus or a connection status.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::ChangeChannel;
+///
+/// let mut focus = ChangeChannel::new();
+/// let mut reader = focus.register_reader();
+///
+/// focus.set(true);
+/// focus.set(true);
+/// focus.set(false);
+///
+/// assert_eq!(focus.current(), Some(&false));
+/// assert_eq!(focus.read(&mut reader).collect::<Vec<_>>(), vec![&true, &false]);
+/// ```
+#[derive(Debug)]
+pub struct ChangeChannel<T> {
+    channel: EventChannel<T>,
+}
+
+impl<T> ChangeChannel<T>
+where
+    T: Event + PartialEq,
+{
+    /// Create a new `ChangeChannel` with a default size of 64.
+    pub fn new() -> Self {
+        Self::from_channel(EventChannel::new())
+    }
+
+    /// Create a new `ChangeChannel` with the given starting capacity.
+    pub fn with_capacity(size: usize) -> Self {
+        Self::from_channel(EventChannel::with_capacity(size))
+    }
+
+    /// Wraps `channel`, comparing new values with the most recent one stored
+    /// in it.
+    pub fn from_channel(channel: EventChannel<T>) -> Self {
+        ChangeChannel { channel }
+    }
+
+    /// Writes `value` if it differs from the current value, returning whether
+    /// it was written.
+    pub fn set(&mut self, value: T) -> bool {
+        if self.current() == Some(&value) {
+            return false;
+        }
+        self.channel.single_write(value);
+
+        true
+    }
+
+    /// Returns the most recently written value, if any.
+    pub fn current(&self) -> Option<&T> {
+        self.channel.at_lag(0)
+    }
+
+    /// Register a new reader.
+    ///
+    /// See `EventChannel::register_reader`.
+    pub fn register_reader(&mut self) -> ReaderId<T> {
+        self.channel.register_reader()
+    }
+
+    /// Read the changes written since the last read with `reader_id`.
+    ///
+    /// See `EventChannel::read`.
+    pub fn read(&self, reader_id: &mut ReaderId<T>) -> EventIterator<'_, T> {
+        self.channel.read(reader_id)
+    }
+
+    /// Returns the underlying channel.
+    pub fn into_inner(self) -> EventChannel<T> {
+        self.channel
+    }
+}
+
+impl<T> Default for ChangeChannel<T>
+where
+    T: Event + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let mut channel = ChangeChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.current(), None);
+
+        assert!(channel.set(1));
+        assert!(!channel.set(1));
+        assert!(!channel.set(1));
+        assert!(channel.set(2));
+        assert!(channel.set(1));
+        assert_eq!(channel.current(), Some(&1));
+        assert_eq!(channel.read(&mut reader).cloned().collect::<Vec<_>>(), [1, 2, 1]);
+
+        // Still compares against the last value after it has been read
+        assert!(!channel.set(1));
+        assert_eq!(channel.read(&mut reader).len(), 0);
+        assert_eq!(channel.into_inner().next_sequence(), 3);
+    }
+}
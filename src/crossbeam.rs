@@ -0,0 +1,86 @@
+//! Moving events between channels and `crossbeam_channel`s, enabled by the
+//! `crossbeam` feature.
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{Event, EventChannel, ReaderId};
+
+impl<E> EventChannel<E>
+where
+    E: Event,
+{
+    /// Write all events which are currently available from `receiver` into
+    /// storage, without blocking.
+    ///
+    /// This is `bridge_from` for a `crossbeam_channel::Receiver`. Returns the
+    /// number of events written.
+    pub fn bridge_from_crossbeam(&mut self, receiver: &Receiver<E>) -> usize {
+        let mut events: Vec<_> = receiver.try_iter().collect();
+        let len = events.len();
+        self.drain_vec_write(&mut events);
+
+        len
+    }
+
+    /// Send clones of the events pending for `reader_id` to `sender`, and
+    /// mark them as read.
+    ///
+    /// This is `forward_to` for a `crossbeam_channel::Sender`. It never
+    /// blocks: if a bounded `sender` is full, or the receiving end has been
+    /// dropped, this stops at the first event which couldn't be sent, and
+    /// keeps it and all later events pending. Returns the number of events
+    /// sent.
+    pub fn forward_to_crossbeam(&self, reader_id: &mut ReaderId<E>, sender: &Sender<E>) -> usize
+    where
+        E: Clone,
+    {
+        let sent = self
+            .peek(reader_id)
+            .take_while(|&event| sender.try_send(event.clone()).is_ok())
+            .count();
+
+        self.ack(reader_id, sent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossbeam_channel::{bounded, unbounded};
+
+    use super::*;
+    use crate::testing::EventChannelExt;
+
+    #[test]
+    fn test_bridge_from() {
+        let (tx, rx) = unbounded();
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+
+        assert_eq!(channel.bridge_from_crossbeam(&rx), 0);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(channel.bridge_from_crossbeam(&rx), 2);
+        drop(tx);
+        assert_eq!(channel.bridge_from_crossbeam(&rx), 0);
+
+        channel.assert_reads(&mut reader, &[1, 2]);
+    }
+
+    #[test]
+    fn test_forward_to() {
+        let (tx, rx) = bounded(2);
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+
+        channel.iter_write(0..3);
+        // The third event doesn't fit and stays pending
+        assert_eq!(channel.forward_to_crossbeam(&mut reader, &tx), 2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(channel.forward_to_crossbeam(&mut reader, &tx), 1);
+
+        channel.single_write(3);
+        drop(rx);
+        assert_eq!(channel.forward_to_crossbeam(&mut reader, &tx), 0);
+        channel.assert_reads(&mut reader, &[3]);
+    }
+}
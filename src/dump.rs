@@ -0,0 +1,56 @@
+//! Snapshots of the state of an `EventChannel`, for debugging tools.
+
+use std::fmt;
+
+use crate::EventIterator;
+
+/// A snapshot of the state of an `EventChannel`, e.g. for showing it in an
+/// inspector or a debug console.
+///
+/// Obtained with `EventChannel::debug_dump`. Positions are given as sequence
+/// numbers, see `EventChannel::next_sequence`. Formatting it with `Display`
+/// lists the readers and the stored events, if `E: Debug`.
+#[derive(Debug)]
+pub struct ChannelDump<'a, E> {
+    /// The sequence number the next event written will get.
+    pub next_sequence: u64,
+    /// The sequence number of the oldest stored event.
+    pub oldest: u64,
+    /// The number of events the channel can store without growing.
+    pub capacity: usize,
+    /// The registered readers, ordered by their id.
+    pub readers: Vec<ReaderDump>,
+    /// The stored events, from the oldest to the most recent one.
+    pub events: EventIterator<'a, E>,
+}
+
+/// The state of a single reader in a `ChannelDump`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReaderDump {
+    /// The id of the reader, see `ReaderId::id`.
+    pub id: usize,
+    /// The sequence number of the next event the reader reads.
+    pub position: u64,
+    /// The number of events the reader has not read yet.
+    pub lag: usize,
+}
+
+impl<E: fmt::Debug> fmt::Display for ChannelDump<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} events written, {} stored, capacity {}",
+            self.next_sequence,
+            self.events.len(),
+            self.capacity
+        )?;
+        for reader in &self.readers {
+            writeln!(f, "reader {}: at {}, lag {}", reader.id, reader.position, reader.lag)?;
+        }
+        for (sequence, event) in (self.oldest..).zip(self.events.clone()) {
+            writeln!(f, "{}: {:?}", sequence, event)?;
+        }
+
+        Ok(())
+    }
+}
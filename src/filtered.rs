@@ -0,0 +1,229 @@
+//! Readers which see a view of the events.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::{Event, EventChannel, ReaderId};
+
+type Filter<E> = Box<dyn Fn(&E) -> bool + Send + Sync>;
+type Projection<E, U> = Box<dyn Fn(&E) -> U + Send + Sync>;
+
+/// The filter of a `FilteredReader`, shared with its channel so the channel
+/// can count the dropped events which match it.
+pub(crate) struct LossFilter<E> {
+    pub(crate) filter: Filter<E>,
+    pub(crate) lost: AtomicU64,
+}
+
+/// A reader which only reads the events matching its filter, obtained from
+/// `EventChannel::register_filtered_reader`.
+///
+/// This allows sending many kinds of events through one channel, with every
+/// reader only seeing the kinds it is interested in.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::EventChannel;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Input {
+///     Key(char),
+///     Click(i32, i32),
+/// }
+///
+/// let mut channel = EventChannel::new();
+/// let mut keys = channel.register_filtered_reader(|e| matches!(e, Input::Key(_)));
+///
+/// channel.iter_write(vec![Input::Key('a'), Input::Click(1, 2), Input::Key('b')]);
+///
+/// assert_eq!(keys.pending(&channel), 2);
+/// assert_eq!(
+///     keys.read(&channel).collect::<Vec<_>>(),
+///     vec![&Input::Key('a'), &Input::Key('b')]
+/// );
+/// ```
+pub struct FilteredReader<E: 'static> {
+    reader_id: ReaderId<E>,
+    filter: Arc<LossFilter<E>>,
+}
+
+impl<E> FilteredReader<E>
+where
+    E: Event,
+{
+    pub(crate) fn new(reader_id: ReaderId<E>, filter: Arc<LossFilter<E>>) -> Self {
+        FilteredReader { reader_id, filter }
+    }
+
+    /// Read the events matching the filter from `channel`, which must be the
+    /// channel this reader was registered with.
+    ///
+    /// The reader is advanced past all events, including the ones which
+    /// don't match.
+    pub fn read<'a>(&'a mut self, channel: &'a EventChannel<E>) -> impl Iterator<Item = &'a E> {
+        let filter = &self.filter.filter;
+
+        channel.read(&mut self.reader_id).filter(move |e| filter(e))
+    }
+
+    /// Returns how many of the events pending in `channel` match the filter.
+    pub fn pending(&self, channel: &EventChannel<E>) -> usize {
+        channel.peek(&self.reader_id).filter(|e| (self.filter.filter)(e)).count()
+    }
+
+    /// Returns the number of events matching the filter which the reader has
+    /// missed since it was registered.
+    ///
+    /// Like `EventChannel::total_lost`, this counts the events dropped
+    /// because of `DropPolicy::DropNewest`, but only the ones matching the
+    /// filter, which the channel checks while dropping them.
+    pub fn total_lost(&self) -> u64 {
+        self.filter.lost.load(Ordering::Relaxed)
+    }
+
+    /// Returns the underlying reader, which sees all events.
+    pub fn reader_id(&self) -> &ReaderId<E> {
+        &self.reader_id
+    }
+
+    /// Unwraps the underlying reader, dropping the filter.
+    pub fn into_reader_id(self) -> ReaderId<E> {
+        self.reader_id
+    }
+}
+
+impl<E: 'static> fmt::Debug for FilteredReader<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FilteredReader")
+            .field("reader_id", &self.reader_id)
+            .finish()
+    }
+}
+
+/// A reader which reads values derived from the events, obtained from
+/// `EventChannel::register_typed_reader`.
+///
+/// This lets code which only needs part of large events, e.g. the ids of
+/// the entities involved in a collision, consume just that.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::EventChannel;
+///
+/// struct Collision {
+///     entities: (u32, u32),
+///     contacts: Vec<[f32; 3]>,
+/// }
+///
+/// let mut channel = EventChannel::new();
+/// let mut entities = channel.register_typed_reader(|c: &Collision| c.entities);
+///
+/// channel.single_write(Collision {
+///     entities: (1, 2),
+///     contacts: vec![[0.0; 3]],
+/// });
+///
+/// assert_eq!(entities.read(&channel).collect::<Vec<_>>(), vec![(1, 2)]);
+/// ```
+pub struct TypedReader<E: 'static, U> {
+    reader_id: ReaderId<E>,
+    projection: Projection<E, U>,
+}
+
+impl<E, U> TypedReader<E, U>
+where
+    E: Event,
+{
+    pub(crate) fn new(reader_id: ReaderId<E>, projection: Projection<E, U>) -> Self {
+        TypedReader {
+            reader_id,
+            projection,
+        }
+    }
+
+    /// Read the values derived from the events pending in `channel`, which
+    /// must be the channel this reader was registered with.
+    pub fn read<'a>(&'a mut self, channel: &'a EventChannel<E>) -> impl Iterator<Item = U> + 'a {
+        channel.read(&mut self.reader_id).map(&self.projection)
+    }
+
+    /// Returns the underlying reader, which reads the events themselves.
+    pub fn reader_id(&self) -> &ReaderId<E> {
+        &self.reader_id
+    }
+
+    /// Unwraps the underlying reader, dropping the projection.
+    pub fn into_reader_id(self) -> ReaderId<E> {
+        self.reader_id
+    }
+}
+
+impl<E: 'static, U> fmt::Debug for TypedReader<E, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TypedReader")
+            .field("reader_id", &self.reader_id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DropPolicy;
+
+    #[test]
+    fn test_read() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut even = channel.register_filtered_reader(|e| e % 2 == 0);
+        let mut all = channel.register_reader();
+
+        channel.iter_write(0..7);
+        assert_eq!(even.pending(&channel), 4);
+        assert_eq!(even.read(&channel).cloned().collect::<Vec<_>>(), [0, 2, 4, 6]);
+        assert_eq!(even.pending(&channel), 0);
+        assert_eq!(channel.reader_lag(&all), 7);
+
+        channel.single_write(7);
+        assert_eq!(even.read(&channel).count(), 0);
+        assert_eq!(even.total_lost(), 0);
+        let mut reader_id = even.into_reader_id();
+        channel.single_write(9);
+        assert_eq!(channel.read(&mut reader_id).cloned().collect::<Vec<_>>(), [9]);
+        assert_eq!(channel.read(&mut all).len(), 9);
+    }
+
+    #[test]
+    fn test_total_lost() {
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        let mut even = channel.register_filtered_reader(|e| e % 2 == 0);
+        let odd = channel.register_filtered_reader(|e| e % 2 == 1);
+
+        // 4 and 6 are dropped
+        channel.iter_write(0..7);
+        assert_eq!((even.total_lost(), odd.total_lost()), (2, 1));
+        assert_eq!(channel.total_lost(even.reader_id()), 3);
+        drop(odd);
+
+        assert_eq!(even.read(&channel).cloned().collect::<Vec<_>>(), [0, 2]);
+        channel.copy_write(&[10, 11, 12, 13, 14, 15]);
+        assert_eq!(even.total_lost(), 2 + 1);
+    }
+
+    #[test]
+    fn test_typed_read() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut lengths = channel.register_typed_reader(String::len);
+
+        channel.iter_write(vec!["a".to_string(), "bcd".to_string()]);
+        assert_eq!(lengths.read(&channel).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(lengths.read(&channel).count(), 0);
+        assert_eq!(channel.reader_lag(lengths.reader_id()), 0);
+    }
+}
@@ -0,0 +1,225 @@
+//! Ring buffer with a capacity fixed at compile time, stored inline.
+
+use std::{fmt, mem::MaybeUninit, ptr};
+
+use crate::Cursor;
+
+/// Ring buffer holding up to `N` events inline, without any heap
+/// allocation.
+///
+/// This is meant for small, hot channels, e.g. the input events of a frame,
+/// which can then live on the stack or inside a component. Since it can't
+/// grow, a write to a full buffer overwrites the oldest event. Events are
+/// read with cursors, which work like the ones of an `EventChannel` (see
+/// `EventChannel::register_cursor`): they aren't registered with the buffer,
+/// and skip the events which were overwritten before they read them.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::FixedRingBuffer;
+///
+/// let mut keys = FixedRingBuffer::<char, 4>::new();
+/// let mut cursor = keys.register_cursor();
+///
+/// keys.iter_write("abcdef".chars());
+///
+/// assert_eq!(keys.lost_preview(&cursor), 2);
+/// assert_eq!(keys.read_cursor(&mut cursor).collect::<String>(), "cdef");
+/// ```
+pub struct FixedRingBuffer<T, const N: usize> {
+    slots: [MaybeUninit<T>; N],
+    /// Total number of events written so far.
+    written: u64,
+    /// Number of events stored, in the slots right before `written % N`.
+    len: usize,
+}
+
+impl<T, const N: usize> FixedRingBuffer<T, N> {
+    /// Creates an empty buffer.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `N` is zero.
+    pub fn new() -> Self {
+        assert!(N > 0, "`FixedRingBuffer` needs a capacity");
+
+        FixedRingBuffer {
+            // An array of `MaybeUninit` doesn't need initialization.
+            slots: unsafe { MaybeUninit::uninit().assume_init() },
+            written: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of events the buffer can hold, `N`.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of events stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no events are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the sequence number the next written event will get, see
+    /// `EventChannel::next_sequence`.
+    pub fn next_sequence(&self) -> u64 {
+        self.written
+    }
+
+    /// Writes a single event, dropping the oldest one if the buffer is full.
+    pub fn single_write(&mut self, event: T) {
+        let slot = &mut self.slots[(self.written % N as u64) as usize];
+        match self.len {
+            len if len == N => unsafe { ptr::drop_in_place(slot.as_mut_ptr()) },
+            _ => self.len += 1,
+        }
+        *slot = MaybeUninit::new(event);
+        self.written += 1;
+    }
+
+    /// Writes all events of `iter`, one after another.
+    pub fn iter_write<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for event in iter {
+            self.single_write(event);
+        }
+    }
+
+    /// Create a new `Cursor` pointing at the end of the buffer.
+    pub fn register_cursor(&self) -> Cursor {
+        Cursor {
+            written: self.written,
+        }
+    }
+
+    /// Read the events still stored which have been written since the last
+    /// read with `cursor`, advancing it to the end of the buffer.
+    pub fn read_cursor(
+        &self,
+        cursor: &mut Cursor,
+    ) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator + '_ {
+        // Clamping also handles cursors of other buffers
+        let start = cursor.written.max(self.oldest()).min(self.written);
+        cursor.written = self.written;
+
+        self.iter_from(start)
+    }
+
+    /// Returns how many events `cursor` missed because they have been
+    /// overwritten or cleared.
+    pub fn lost_preview(&self, cursor: &Cursor) -> u64 {
+        self.oldest().saturating_sub(cursor.written)
+    }
+
+    /// Iterates over all stored events, from the oldest to the most recent
+    /// one.
+    pub fn iter_all(&self) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator + '_ {
+        self.iter_from(self.oldest())
+    }
+
+    /// Drops all stored events.
+    pub fn clear(&mut self) {
+        for n in self.oldest()..self.written {
+            let index = (n % N as u64) as usize;
+            unsafe { ptr::drop_in_place(self.slots[index].as_mut_ptr()) };
+        }
+        self.len = 0;
+    }
+
+    fn oldest(&self) -> u64 {
+        self.written - self.len as u64
+    }
+
+    fn iter_from(
+        &self,
+        start: u64,
+    ) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator + '_ {
+        let len = (self.written - start) as usize;
+
+        // Only stored events are in range.
+        (0..len).map(move |n| {
+            let index = ((start + n as u64) % N as u64) as usize;
+            unsafe { &*self.slots[index].as_ptr() }
+        })
+    }
+}
+
+impl<T, const N: usize> Default for FixedRingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for FixedRingBuffer<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for FixedRingBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedRingBuffer")
+            .field("capacity", &N)
+            .field("written", &self.written)
+            .field("events", &self.iter_all().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_overwrite() {
+        let mut buffer = FixedRingBuffer::<u32, 3>::new();
+        let mut cursor = buffer.register_cursor();
+        let mut late = buffer.register_cursor();
+        assert!(buffer.is_empty());
+
+        buffer.iter_write(0..2);
+        assert_eq!(
+            buffer.read_cursor(&mut cursor).collect::<Vec<_>>(),
+            [&0, &1]
+        );
+        buffer.iter_write(2..5);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.lost_preview(&late), 2);
+        assert_eq!(
+            buffer.read_cursor(&mut late).rev().collect::<Vec<_>>(),
+            [&4, &3, &2]
+        );
+        assert_eq!(buffer.read_cursor(&mut cursor).len(), 3);
+        assert_eq!(buffer.iter_all().collect::<Vec<_>>(), [&2, &3, &4]);
+        assert_eq!(buffer.next_sequence(), 5);
+
+        buffer.clear();
+        buffer.single_write(5);
+        assert_eq!(buffer.read_cursor(&mut cursor).collect::<Vec<_>>(), [&5]);
+    }
+
+    #[test]
+    fn test_drops() {
+        let event = Rc::new(());
+        let mut buffer = FixedRingBuffer::<_, 2>::new();
+        buffer.iter_write(vec![event.clone(); 3]);
+        assert_eq!(Rc::strong_count(&event), 3);
+        buffer.clear();
+        assert_eq!(Rc::strong_count(&event), 1);
+
+        buffer.single_write(event.clone());
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&event), 1);
+    }
+}
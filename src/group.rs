@@ -0,0 +1,197 @@
+//! Readers shared by several consumers.
+
+use crate::{AtomicReaderId, Event, SyncEventChannel};
+
+/// A reader shared by a group of consumers, e.g. worker threads, so every
+/// event is handled by exactly one of them.
+///
+/// An ordinary reader receives every event, so every consumer with its own
+/// reader sees every event. The members of a group compete for the events
+/// instead: whoever asks first with `next` or `next_batch` gets the next
+/// event, and no other member will. The group counts as a single reader of
+/// the channel, which keeps the events until some member took them.
+///
+/// The group is an `AtomicReaderId`, whose position is the single cursor of
+/// all members: taking events claims them by moving it forward with a
+/// compare-and-swap. Taking from a `SyncEventChannel` still locks it to clone
+/// the events; members with shared access to the `EventChannel`, e.g. behind
+/// an `RwLock`, can claim events at the same time with
+/// `EventChannel::read_atomic_batch` and `reader_id`.
+///
+/// ## Examples
+///
+/// ```
+/// use std::{sync::Arc, thread};
+///
+/// use shrev::{EventChannel, ReaderGroup, SyncEventChannel};
+///
+/// let channel = Arc::new(SyncEventChannel::new(EventChannel::new()));
+/// let jobs = Arc::new(ReaderGroup::new(&channel));
+/// channel.iter_write(0..100);
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let (channel, jobs) = (channel.clone(), jobs.clone());
+///         thread::spawn(move || {
+///             let mut done = 0;
+///             while let Some(job) = jobs.next(&channel) {
+///                 done += job;
+///             }
+///             done
+///         })
+///     })
+///     .collect();
+///
+/// let total: i32 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+/// assert_eq!(total, (0..100).sum());
+/// ```
+#[derive(Debug)]
+pub struct ReaderGroup<E: 'static> {
+    reader_id: AtomicReaderId<E>,
+}
+
+impl<E> ReaderGroup<E>
+where
+    E: Event,
+{
+    /// Registers a new group with `channel`, which receives the events
+    /// written after its creation.
+    pub fn new(channel: &SyncEventChannel<E>) -> Self {
+        Self::from_reader(channel.lock().register_atomic_reader())
+    }
+
+    /// Turns `reader_id` into a group, which starts where `reader_id` is.
+    pub fn from_reader(reader_id: AtomicReaderId<E>) -> Self {
+        ReaderGroup { reader_id }
+    }
+
+    /// Takes the next event from `channel`, which must be the channel the
+    /// group reads from, or returns `None` if there is none.
+    pub fn next(&self, channel: &SyncEventChannel<E>) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.next_batch(channel, 1).pop()
+    }
+
+    /// Takes up to `max` of the next events from `channel` at once.
+    ///
+    /// Taking several events at a time is cheaper, but the events of a batch
+    /// won't be handled concurrently.
+    pub fn next_batch(&self, channel: &SyncEventChannel<E>, max: usize) -> Vec<E>
+    where
+        E: Clone,
+    {
+        let channel = channel.lock();
+
+        channel.read_atomic_batch(&self.reader_id, max).cloned().collect()
+    }
+
+    /// Returns how many events are left for the members to take.
+    pub fn pending(&self, channel: &SyncEventChannel<E>) -> usize {
+        channel.lock().atomic_reader_lag(&self.reader_id)
+    }
+
+    /// Returns the reader shared by the members.
+    pub fn reader_id(&self) -> &AtomicReaderId<E> {
+        &self.reader_id
+    }
+
+    /// Unwraps the reader of the group.
+    pub fn into_reader_id(self) -> AtomicReaderId<E> {
+        self.reader_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, RwLock},
+        thread,
+    };
+
+    use super::*;
+    use crate::EventChannel;
+
+    #[test]
+    fn test_next_batch() {
+        let channel = SyncEventChannel::new(EventChannel::new());
+        let group = ReaderGroup::new(&channel);
+        assert_eq!(group.next(&channel), None);
+
+        channel.iter_write(0..5);
+        assert_eq!(group.pending(&channel), 5);
+        assert_eq!(group.next(&channel), Some(0));
+        assert_eq!(group.next_batch(&channel, 3), [1, 2, 3]);
+        assert_eq!(group.next_batch(&channel, 3), [4]);
+        assert_eq!(group.pending(&channel), 0);
+
+        channel.single_write(5);
+        let reader_id = group.into_reader_id();
+        let read: Vec<_> = channel.lock().read_atomic(&reader_id).cloned().collect();
+        assert_eq!(read, [5]);
+    }
+
+    #[test]
+    fn test_shared_channel() {
+        let channel = Arc::new(RwLock::new(EventChannel::with_capacity(4)));
+        let group = Arc::new(ReaderGroup::from_reader(
+            channel.write().unwrap().register_atomic_reader(),
+        ));
+        channel.write().unwrap().iter_write(0..1000);
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let (channel, group) = (channel.clone(), group.clone());
+                thread::spawn(move || {
+                    let mut taken = vec![];
+                    loop {
+                        let channel = channel.read().unwrap();
+                        let batch = channel.read_atomic_batch(group.reader_id(), 7);
+                        match batch.len() {
+                            0 => return taken,
+                            _ => taken.extend(batch.cloned()),
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut taken: Vec<_> = workers.into_iter().flat_map(|w| w.join().unwrap()).collect();
+        taken.sort_unstable();
+        assert_eq!(taken, (0..1000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exactly_once() {
+        let channel = Arc::new(SyncEventChannel::new(EventChannel::with_capacity(8)));
+        let group = Arc::new(ReaderGroup::new(&channel));
+
+        let workers: Vec<_> = (0..4)
+            .map(|worker| {
+                let (channel, group) = (channel.clone(), group.clone());
+                thread::spawn(move || {
+                    let mut taken = vec![];
+                    while !taken.contains(&u32::MAX) {
+                        match worker % 2 {
+                            0 => taken.extend(group.next(&channel)),
+                            _ => taken.extend(group.next_batch(&channel, 3)),
+                        }
+                        thread::yield_now();
+                    }
+                    taken
+                })
+            })
+            .collect();
+
+        channel.iter_write(0..1000);
+        // Workers stop after taking an end marker, and a batch may take
+        // three of them.
+        channel.iter_write(vec![u32::MAX; 4 * 3]);
+
+        let mut taken: Vec<_> = workers.into_iter().flat_map(|w| w.join().unwrap()).collect();
+        taken.sort_unstable();
+        taken.truncate(1000);
+        assert_eq!(taken, (0..1000).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,117 @@
+//! Using channels of bytes as `std::io` streams.
+
+use std::io::{self, Read, Write};
+
+use crate::{EventChannel, ReaderId};
+
+/// Writes bytes into the channel as individual events.
+///
+/// Writing never fails. Without readers, the channel only keeps the most
+/// recent bytes, as many as its capacity; otherwise it grows so every reader
+/// receives all bytes.
+impl Write for EventChannel<u8> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.iter_write(buf.iter().cloned());
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads the bytes pending for a reader of a byte channel.
+///
+/// Reading only advances the reader past the bytes which were copied into the
+/// buffer; if there are no bytes pending, a read returns 0.
+///
+/// ## Examples
+///
+/// ```
+/// use std::io::{Read, Write};
+///
+/// use shrev::{EventChannel, ReaderCursor};
+///
+/// let mut channel = EventChannel::new();
+/// let mut reader = channel.register_reader();
+/// channel.write_all(b"some bytes").unwrap();
+///
+/// let mut text = String::new();
+/// ReaderCursor::new(&channel, &mut reader)
+///     .read_to_string(&mut text)
+///     .unwrap();
+/// assert_eq!(text, "some bytes");
+/// ```
+#[derive(Debug)]
+pub struct ReaderCursor<'a> {
+    channel: &'a EventChannel<u8>,
+    reader_id: &'a mut ReaderId<u8>,
+}
+
+impl<'a> ReaderCursor<'a> {
+    /// Creates a cursor reading the bytes of `channel` pending for
+    /// `reader_id`.
+    pub fn new(channel: &'a EventChannel<u8>, reader_id: &'a mut ReaderId<u8>) -> Self {
+        ReaderCursor { channel, reader_id }
+    }
+}
+
+impl Read for ReaderCursor<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (first, second) = self.channel.peek(self.reader_id).as_slices();
+
+        let mut copied = 0;
+        for bytes in [first, second].iter() {
+            let len = bytes.len().min(buf.len() - copied);
+            buf[copied..copied + len].copy_from_slice(&bytes[..len]);
+            copied += len;
+        }
+
+        Ok(self.channel.ack(self.reader_id, copied))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let mut channel = EventChannel::with_capacity(8);
+        let mut reader = channel.register_reader();
+
+        write!(channel, "hello {}", 42).unwrap();
+        assert_eq!(channel.read(&mut reader).cloned().collect::<Vec<_>>(), b"hello 42");
+    }
+
+    #[test]
+    fn test_read() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.write_all(b"abc").unwrap();
+
+        let mut buf = [0; 2];
+        assert_eq!(ReaderCursor::new(&channel, &mut reader).read(&mut buf).unwrap(), 2);
+        assert_eq!(&buf, b"ab");
+
+        // Wraps around the end of the ring buffer
+        channel.write_all(b"def").unwrap();
+        let mut rest = vec![];
+        ReaderCursor::new(&channel, &mut reader)
+            .read_to_end(&mut rest)
+            .unwrap();
+        assert_eq!(rest, b"cdef");
+        assert_eq!(ReaderCursor::new(&channel, &mut reader).read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_write_keeps_last_bytes() {
+        let mut channel = EventChannel::with_capacity(4);
+
+        channel.write_all(b"abcdef").unwrap();
+        writeln!(channel, "g").unwrap();
+        channel.flush().unwrap();
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), b"efg\n");
+    }
+}
@@ -0,0 +1,104 @@
+//! Channels which only keep the latest value.
+
+use crate::{Event, EventChannel, ReaderId};
+
+/// A channel which only keeps the most recently written value.
+///
+/// Unlike with an `EventChannel`, readers don't receive every value written:
+/// a read returns the latest value if it has been written since the previous
+/// read, and `None` otherwise. This is meant for values where only the most
+/// recent one matters, like a configuration which can be reloaded. Writing
+/// doesn't compare values, see `ChangeChannel` for that.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::LatestChannel;
+///
+/// let mut volume = LatestChannel::new();
+/// let mut reader = volume.register_reader();
+///
+/// volume.write(3);
+/// volume.write(7);
+///
+/// assert_eq!(volume.read(&mut reader), Some(&7));
+/// assert_eq!(volume.read(&mut reader), None);
+/// ```
+#[derive(Debug)]
+pub struct LatestChannel<T> {
+    channel: EventChannel<T>,
+}
+
+impl<T> LatestChannel<T>
+where
+    T: Event,
+{
+    /// Create a new, empty `LatestChannel`.
+    pub fn new() -> Self {
+        LatestChannel {
+            channel: EventChannel::with_capacity(2),
+        }
+    }
+
+    /// Replaces the latest value with `value`.
+    pub fn write(&mut self, value: T) {
+        // Readers which haven't read the previous value skip it, so the
+        // channel never has to store more than two values.
+        self.channel.storage.readers_to_end();
+        self.channel.single_write(value);
+    }
+
+    /// Returns the most recently written value, if any.
+    pub fn latest(&self) -> Option<&T> {
+        self.channel.at_lag(0)
+    }
+
+    /// Register a new reader.
+    ///
+    /// The reader only sees values written after it has been registered.
+    pub fn register_reader(&mut self) -> ReaderId<T> {
+        self.channel.register_reader()
+    }
+
+    /// Returns the latest value if it has been written since the last read
+    /// with `reader_id`.
+    pub fn read(&self, reader_id: &mut ReaderId<T>) -> Option<&T> {
+        self.channel.read(reader_id).next_back()
+    }
+}
+
+impl<T> Default for LatestChannel<T>
+where
+    T: Event,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_read() {
+        let mut channel = LatestChannel::new();
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.read(&mut reader), None);
+        assert_eq!(channel.latest(), None);
+
+        for value in 0..10 {
+            channel.write(value);
+        }
+        let mut late = channel.register_reader();
+        assert_eq!(channel.read(&mut reader), Some(&9));
+        assert_eq!(channel.read(&mut reader), None);
+        assert_eq!(channel.read(&mut late), None);
+
+        channel.write(9);
+        assert_eq!(channel.read(&mut reader), Some(&9));
+        assert_eq!(channel.read(&mut late), Some(&9));
+        assert_eq!(channel.latest(), Some(&9));
+        assert_eq!(channel.channel.storage.capacity(), 2);
+    }
+}
@@ -5,11 +5,74 @@
 
 #![warn(missing_docs)]
 
-pub use crate::storage::{ReaderId, StorageIterator as EventIterator};
+pub use crate::{
+    any::AnyChannel,
+    atomic::AtomicRingBuffer,
+    builder::{BuildError, ChannelBuilder},
+    change::ChangeChannel,
+    dump::{ChannelDump, ReaderDump},
+    filtered::{FilteredReader, TypedReader},
+    fixed::FixedRingBuffer,
+    group::ReaderGroup,
+    io::ReaderCursor,
+    latest::LatestChannel,
+    partition::{PartitionedChannel, PartitionedReader},
+    priority::{Priority, PriorityChannel, PriorityReader},
+    record::Recorder,
+    registry::ChannelRegistry,
+    select::Select,
+    split::{ReaderFactory, Writer},
+    spsc::{spsc_channel, SpscReader, SpscWriter},
+    stamped::{Stamped, StampedChannel},
+    stats::{BufferHealth, Stats},
+    storage::{
+        AtomicReaderId, CheckpointExpired, CommitToken, Cursor, DropPolicy, EventHandle, EventRange,
+        ReaderCheckpoint, ReaderId, SnapshotReader, StaleCommit, StorageIterator as EventIterator,
+    },
+    sync::SyncEventChannel,
+    txn::WriteTxn,
+};
 
-use crate::storage::RingBuffer;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::{
+        atomic::AtomicU64,
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
+    time::Duration,
+};
 
+use crate::{filtered::LossFilter, storage::RingBuffer};
+
+mod any;
+mod atomic;
+mod builder;
+mod change;
+#[cfg(feature = "crossbeam")]
+mod crossbeam;
+mod dump;
+mod filtered;
+mod fixed;
+mod group;
+mod io;
+mod latest;
+mod partition;
+mod priority;
+mod record;
+mod registry;
+mod select;
+mod split;
+mod spsc;
+mod stamped;
+mod stats;
 mod storage;
+mod sync;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod txn;
 mod util;
 
 /// Marker trait for data to use with the EventChannel.
@@ -21,6 +84,32 @@ impl<T> Event for T where T: 'static {}
 
 const DEFAULT_CAPACITY: usize = 64;
 
+/// Computes a capacity for `EventChannel::with_capacity` which can hold
+/// `retention` worth of events written at `events_per_sec`.
+///
+/// The result is rounded up and at least as large as the smallest capacity a
+/// channel can have.
+///
+/// ## Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use shrev::{recommend_capacity, EventChannel};
+///
+/// // Keep a second of input events at 60 frames per second
+/// let capacity = recommend_capacity(60.0, Duration::from_secs(1));
+/// assert_eq!(capacity, 60);
+///
+/// let _channel = EventChannel::<u32>::with_capacity(capacity);
+/// ```
+pub fn recommend_capacity(events_per_sec: f64, retention: Duration) -> usize {
+    let events = (events_per_sec * retention.as_secs_f64()).ceil();
+
+    // Casting saturates, and turns NaN into 0
+    (events as usize).max(2)
+}
+
 /// The `EventChannel`, which is the central component of `shrev`.
 ///
 /// ## How it works
@@ -87,11 +176,34 @@ const DEFAULT_CAPACITY: usize = 64;
 /// // any events
 /// drop(second_reader);
 /// ```
+///
+/// Reading only needs a shared reference, so any number of threads can read
+/// from a channel at once, each with its own `ReaderId`; see
+/// `SyncEventChannel` for writing from several threads.
+///
+/// Cloning a channel clones the events it stores, but none of its readers,
+/// taps or hooks. `clone_from` reuses the memory of the target channel, and
+/// moves its readers to the end.
 #[derive(Debug)]
 pub struct EventChannel<E> {
     storage: RingBuffer<E>,
 }
 
+impl<E> Clone for EventChannel<E>
+where
+    E: Event + Clone,
+{
+    fn clone(&self) -> Self {
+        EventChannel {
+            storage: self.storage.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.storage.clone_from(&source.storage);
+    }
+}
+
 impl<E> Default for EventChannel<E>
 where
     E: Event,
@@ -117,6 +229,23 @@ where
         }
     }
 
+    /// Create a new `EventChannel` with the given starting capacity, which
+    /// handles writes that don't fit according to `policy`.
+    ///
+    /// With `DropPolicy::DropNewest`, the channel never grows; events which
+    /// would overwrite unread ones are dropped instead.
+    pub fn with_policy(size: usize, policy: DropPolicy) -> Self {
+        Self {
+            storage: RingBuffer::with_policy(size, policy),
+        }
+    }
+
+    /// Returns a builder to configure a new `EventChannel`, which checks the
+    /// configuration instead of panicking.
+    pub fn builder() -> ChannelBuilder {
+        ChannelBuilder::new()
+    }
+
     /// Returns `true` if any reader would observe an additional event.
     ///
     /// This can be used to skip calls to `iter_write` in case the event
@@ -125,6 +254,18 @@ where
         self.storage.would_write()
     }
 
+    /// Performs deferred housekeeping.
+    ///
+    /// Dropping a `ReaderId` only notifies the channel; the reader is
+    /// unregistered the next time the channel needs to know about its
+    /// readers, e.g. when the buffer is full or a reader is registered. This
+    /// method unregisters dropped readers right away, which is useful before
+    /// inspecting the readers (e.g. with `reader_positions`) and can be done
+    /// once per frame to keep the work out of writes.
+    pub fn maintain(&mut self) {
+        self.storage.maintain();
+    }
+
     /// Register a new reader.
     ///
     /// To be able to read events, a reader id is required. This is because
@@ -141,6 +282,74 @@ where
         self.storage.new_reader_id()
     }
 
+    /// Register a new reader like `register_reader`, labeled with `label`.
+    ///
+    /// The label is included in the `Debug` output of the `ReaderId`, which
+    /// helps telling readers apart in logs.
+    pub fn register_reader_labeled(&mut self, label: &'static str) -> ReaderId<E> {
+        self.storage.new_reader_id_labeled(label)
+    }
+
+    /// Register a new reader which only reads the events for which `filter`
+    /// returns `true`.
+    ///
+    /// Like any reader, it keeps all events stored until it has read past
+    /// them, including the ones which don't match.
+    /// `FilteredReader::total_lost` only counts the dropped events which
+    /// match.
+    pub fn register_filtered_reader<F>(&mut self, filter: F) -> FilteredReader<E>
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        let filter = Arc::new(LossFilter {
+            filter: Box::new(filter),
+            lost: AtomicU64::new(0),
+        });
+        self.storage.add_loss_filter(Arc::downgrade(&filter));
+
+        FilteredReader::new(self.register_reader(), filter)
+    }
+
+    /// Register a new reader which reads the values `projection` derives
+    /// from the events, instead of the events themselves.
+    ///
+    /// See `read_map` for doing this for a single read.
+    pub fn register_typed_reader<U, F>(&mut self, projection: F) -> TypedReader<E, U>
+    where
+        F: Fn(&E) -> U + Send + Sync + 'static,
+    {
+        TypedReader::new(self.register_reader(), Box::new(projection))
+    }
+
+    /// Register a new reader which never reads more than the `max_age` most
+    /// recent events, e.g. for a real-time consumer which has no use for
+    /// stale input.
+    ///
+    /// Whenever the reader has more events pending, reading it skips the
+    /// older ones, and `reader_lag` doesn't count them. The channel still
+    /// keeps the skipped events stored until the reader moves past them.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_age` is zero, since such a reader would never read
+    /// anything.
+    pub fn register_reader_max_age(&mut self, max_age: usize) -> ReaderId<E> {
+        self.storage.new_reader_id_max_age(max_age)
+    }
+
+    /// Register a new reader which receives the events from the sequence
+    /// number `sequence` on, e.g. to resume a consumer which persisted the
+    /// sequence number of the next event it needs.
+    ///
+    /// If the channel no longer stores the event with that sequence number,
+    /// the reader starts at the oldest stored event instead; compare
+    /// `reader_lag` with the expected number of events to detect this. A
+    /// sequence number which has not been written yet makes the reader start
+    /// at the end. See `next_sequence` for how events are numbered.
+    pub fn reader_at_sequence(&mut self, sequence: u64) -> ReaderId<E> {
+        self.storage.new_reader_id_at(sequence)
+    }
+
     /// Duplicate an existing reader.
     ///
     /// Creating a new reader only allows for that reader to read events
@@ -157,16 +366,166 @@ where
         self.storage.duplicate_reader_id(reader_id)
     }
 
+    /// Returns the number of events `reader_id` would receive if it would
+    /// `read` now.
+    pub fn reader_lag(&self, reader_id: &ReaderId<E>) -> usize {
+        self.storage.reader_lag(reader_id)
+    }
+
+    /// Returns the number of events written since `reader_id` last read,
+    /// no matter how many events that read returned.
+    ///
+    /// Unlike `reader_lag`, this keeps growing for a reader which is no
+    /// longer being read from, even if it only acknowledges some of its
+    /// events each time. Registering a reader counts as reading.
+    pub fn reader_staleness(&self, reader_id: &ReaderId<E>) -> u64 {
+        self.storage.reader_staleness(reader_id)
+    }
+
+    /// Returns the number of events `reader_id` has missed since it was
+    /// registered.
+    ///
+    /// Readers only miss events if the channel uses `DropPolicy::DropNewest`
+    /// and drops events because it is full; every reader misses those.
+    /// A duplicated reader counts the misses of the original one.
+    pub fn total_lost(&self, reader_id: &ReaderId<E>) -> u64 {
+        self.storage.reader_total_lost(reader_id)
+    }
+
+    /// Returns `true` if `reader_id` has read all events written so far.
+    pub fn reader_caught_up(&self, reader_id: &ReaderId<E>) -> bool {
+        self.reader_lag(reader_id) == 0
+    }
+
+    /// Returns the lag of the reader which is furthest behind, or zero if
+    /// there are no readers.
+    ///
+    /// A reader which keeps lagging behind is probably not being read from,
+    /// which makes the channel grow.
+    pub fn slowest_reader_lag(&mut self) -> usize {
+        self.storage.slowest_reader_lag()
+    }
+
+    /// Summarizes how close the slowest reader is to filling the channel.
+    ///
+    /// This is meant for monitoring; see `BufferHealth` for the states.
+    pub fn health(&mut self) -> BufferHealth {
+        let lag = self.slowest_reader_lag();
+
+        BufferHealth::from_lag(lag, self.storage.capacity())
+    }
+
+    /// Returns the share of the capacity taken up by events the slowest
+    /// reader hasn't read yet, from `0.0` for no pending events to `1.0` for
+    /// a full channel.
+    ///
+    /// This is the number behind `health`, for exporting it as a metric.
+    pub fn utilization(&mut self) -> f64 {
+        let lag = self.slowest_reader_lag();
+
+        lag as f64 / self.storage.capacity() as f64
+    }
+
+    /// Returns an iterator over the `(id, lag)` pairs of all readers, where
+    /// `id` is what `ReaderId::id` returns for the reader.
+    ///
+    /// This is intended for finding readers which got stuck.
+    pub fn reader_positions(&mut self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.storage.reader_positions()
+    }
+
+    /// Returns a snapshot of the channel: its sequence numbers, capacity,
+    /// the position and lag of every reader, and the stored events.
+    ///
+    /// This is meant for debugging tools, like inspectors or console
+    /// commands, which visualize the state of a channel. See `ChannelDump`.
+    pub fn debug_dump(&mut self) -> ChannelDump<'_, E> {
+        self.storage.debug_dump()
+    }
+
+    /// Returns the sequence number the next written event will get.
+    ///
+    /// Events are numbered in the order they're written, starting with zero
+    /// for the first event written to the channel. Thus, this is also the
+    /// number of events written so far.
+    pub fn next_sequence(&self) -> u64 {
+        self.storage.written()
+    }
+
+    /// Register a new reader, like `register_reader` does, after checking
+    /// that `reader_id` belongs to this channel.
+    ///
+    /// Unlike `duplicate_reader`, the new reader doesn't take over the
+    /// position of `reader_id`: it only receives events written after its
+    /// creation.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `reader_id` was not registered with this channel.
+    pub fn clone_reader_at_head(&mut self, reader_id: &ReaderId<E>) -> ReaderId<E> {
+        self.storage.clone_reader_at_head(reader_id)
+    }
+
+    /// Moves all readers back to the oldest event still stored in the
+    /// channel, so their next `read` returns every stored event.
+    ///
+    /// This is a coarse recovery tool, e.g. for replaying recent history to
+    /// all consumers after they have been paused and reset. Events are
+    /// returned again to readers which have read them before.
+    pub fn resync_all_readers(&mut self) {
+        self.storage.readers_to_oldest();
+    }
+
+    /// Unregisters `reader_id` right away, instead of on the next
+    /// `maintain`.
+    ///
+    /// Its id is reused by the next reader registered.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `reader_id` was not registered with this channel.
+    pub fn purge_reader(&mut self, reader_id: ReaderId<E>) {
+        self.storage.purge_reader_id(reader_id);
+    }
+
+    /// Takes over a reader which may have been registered with a different
+    /// channel.
+    ///
+    /// A reader of this channel is returned unchanged. For any other reader,
+    /// a new one with the same label is registered, which only receives
+    /// events written after this call: positions in different channels are
+    /// unrelated, so they are not carried over. The foreign reader is
+    /// dropped, unregistering it from its channel.
+    pub fn adopt_reader(&mut self, reader_id: ReaderId<E>) -> ReaderId<E> {
+        self.storage.adopt_reader_id(reader_id)
+    }
+
     /// Write a slice of events into storage
     #[deprecated(note = "please use `iter_write` instead")]
     pub fn slice_write(&mut self, events: &[E])
     where
         E: Clone,
     {
-        self.storage.iter_write(events.into_iter().cloned());
+        self.storage.iter_write(events.iter().cloned());
+    }
+
+    /// Write copies of a slice of events into storage, at once.
+    ///
+    /// This is faster than `iter_write` for many events, since they are
+    /// copied in bulk instead of one by one. Taps, an ingest filter, a
+    /// recycle sink or pausing make the channel fall back to `iter_write`.
+    pub fn copy_write(&mut self, events: &[E])
+    where
+        E: Copy,
+    {
+        self.storage.copy_write(events);
     }
 
     /// Write an iterator of events into storage
+    ///
+    /// Writing an empty iterator does nothing at all; it isn't counted in the
+    /// `stats` either. Use `set_empty_write_hook` if you need to notice such
+    /// writes.
     pub fn iter_write<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = E>,
@@ -175,174 +534,2782 @@ where
         self.storage.iter_write(iter);
     }
 
-    /// Drain a vector of events into storage.
-    pub fn drain_vec_write(&mut self, events: &mut Vec<E>) {
-        self.storage.drain_vec_write(events);
+    /// Write clones of the most recent events of `events` into storage, at
+    /// most as many as the channel has capacity for.
+    ///
+    /// This is meant for using the channel as a sliding window: in contrast
+    /// to `iter_write`, an oversized batch is truncated to `capacity()`
+    /// events, skipping the oldest ones. Readers will never observe skipped
+    /// events.
+    ///
+    /// Only the batch itself is truncated: events readers haven't read yet
+    /// are kept like with `iter_write`, so with `DropPolicy::Grow`, the ring
+    /// buffer can still grow to keep them.
+    pub fn append_clamped(&mut self, events: &[E])
+    where
+        E: Clone,
+    {
+        self.storage.append_clamped(events);
     }
 
-    /// Write a single event into storage.
-    pub fn single_write(&mut self, event: E) {
-        self.storage.single_write(event);
+    /// Drain a vector of events into storage, dropping the oldest events of
+    /// it if there are more than the channel has capacity for.
+    ///
+    /// This is the draining counterpart of `append_clamped`. Returns the
+    /// number of events which were dropped without ever being written,
+    /// including the ones dropped because of `DropPolicy::DropNewest`.
+    pub fn write_lossy(&mut self, events: &mut Vec<E>) -> usize {
+        self.storage.write_lossy(events)
     }
 
-    /// Read any events that have been written to storage since the last read
-    /// with `reader_id` (or the creation of the `ReaderId`, if it hasn't read
-    /// yet).
+    /// Sets a function which gets called for every write of zero events,
+    /// e.g. for logging them.
     ///
-    /// Note that this will advance the position of the reader regardless of
-    /// what you do with the iterator. In other words, calling `read`
-    /// without iterating the result won't preserve the events returned. You
-    /// need to iterate all the events as soon as you got them from this
-    /// method. This behavior is equivalent to e.g. `Vec::drain`.
-    pub fn read(&self, reader_id: &mut ReaderId<E>) -> EventIterator<E> {
-        self.storage.read(reader_id)
+    /// Replaces the previously set function, if any.
+    pub fn set_empty_write_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.storage.set_empty_write_hook(Box::new(hook));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Adds a function which gets called with every event written, right
+    /// when it is written.
+    ///
+    /// Unlike a reader, a tap doesn't need to be polled and doesn't keep
+    /// events in the channel, which makes it a good fit for side effects like
+    /// logging. Taps are called in the order they were added. Events dropped
+    /// because of the `DropPolicy` are not passed to them.
+    pub fn add_tap<F>(&mut self, tap: F)
+    where
+        F: FnMut(&E) + Send + 'static,
+    {
+        self.storage.add_tap(Box::new(tap));
+    }
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Test {
-        pub id: u32,
+    /// Removes all taps added with `add_tap`.
+    pub fn clear_taps(&mut self) {
+        self.storage.clear_taps();
     }
 
-    #[test]
-    fn test_grow() {
-        let mut channel = EventChannel::with_capacity(10);
+    /// Sets a function which all written events are passed through before
+    /// they are stored, e.g. to validate or sanitize them.
+    ///
+    /// The function returns the event to store, which can differ from the
+    /// one it got, or `None` to drop it. This applies to all writes, each
+    /// event of a batch is passed in separately. Dropped events are not
+    /// counted anywhere; a write whose events are all dropped does nothing.
+    ///
+    /// Replaces the previously set function, if any.
+    pub fn set_ingest_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(E) -> Option<E> + Send + 'static,
+    {
+        self.storage.set_ingest_filter(Box::new(filter));
+    }
 
-        let mut reader0 = channel.register_reader();
-        let mut reader1 = channel.register_reader();
+    /// Pauses writing to the channel, or resumes it.
+    ///
+    /// While paused, the channel queues all written events without storing
+    /// them, so readers won't see them yet, e.g. during a state transition.
+    /// Resuming writes the queued events at once, in the order they were
+    /// written, and only then applies the `DropPolicy` and calls taps. The
+    /// queue is not bounded, so don't keep the channel paused for long.
+    ///
+    /// `single_write_handle` returns `None` for events written while paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.storage.set_paused(paused);
+    }
 
-        channel.iter_write(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    /// Checks if writing is paused, see `set_paused`.
+    pub fn is_paused(&self) -> bool {
+        self.storage.is_paused()
+    }
 
-        let data = channel.read(&mut reader0).cloned().collect::<Vec<_>>();
-        assert_eq!(data, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    /// Sets a function which receives every event that gets overwritten by
+    /// a write, instead of dropping it.
+    ///
+    /// This allows reusing allocations owned by events which have been read
+    /// by everyone, e.g. by putting them back into a pool. Events still
+    /// stored when the channel is dropped are dropped as usual.
+    ///
+    /// Replaces the previously set function, if any.
+    pub fn set_recycle_sink<F>(&mut self, sink: F)
+    where
+        F: FnMut(E) + Send + 'static,
+    {
+        self.storage.set_recycle_sink(Box::new(sink));
+    }
 
-        channel.iter_write(vec![9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]);
+    /// Drain a vector of events into storage.
+    ///
+    /// To keep the events around, write clones of them with
+    /// `iter_write(events.iter().cloned())` instead.
+    pub fn drain_vec_write(&mut self, events: &mut Vec<E>) {
+        self.storage.drain_vec_write(events);
+    }
 
-        let data = channel.read(&mut reader0).cloned().collect::<Vec<_>>();
-        assert_eq!(
-            data,
-            vec![9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]
-        );
+    /// Write all events which are currently available from `receiver` into
+    /// storage, without blocking.
+    ///
+    /// The events are written at once, like with `drain_vec_write`. Returns
+    /// the number of events written.
+    ///
+    /// There is no separate pump: calling `bridge_from` and `forward_to`
+    /// once per frame moves everything pending in both directions. With the
+    /// `crossbeam` feature, `bridge_from_crossbeam` and
+    /// `forward_to_crossbeam` do the same for `crossbeam_channel`s.
+    pub fn bridge_from(&mut self, receiver: &Receiver<E>) -> usize {
+        let mut events: Vec<_> = receiver.try_iter().collect();
+        let len = events.len();
+        self.drain_vec_write(&mut events);
 
-        for i in 23..10_000 {
-            channel.single_write(i);
-        }
+        len
+    }
 
-        let data = channel.read(&mut reader1).cloned().collect::<Vec<_>>();
-        assert_eq!(data, (1..10_000).collect::<Vec<_>>());
+    /// Send clones of the events pending for `reader_id` to `sender`, e.g.
+    /// to hand them to code on another thread, and mark them as read.
+    ///
+    /// Returns the number of events sent. If the receiving end has been
+    /// dropped, this stops at the first event which couldn't be sent, and
+    /// keeps it and all later events pending. See `bridge_from` for the
+    /// other direction.
+    pub fn forward_to(&self, reader_id: &mut ReaderId<E>, sender: &Sender<E>) -> usize
+    where
+        E: Clone,
+    {
+        let sent = self
+            .peek(reader_id)
+            .take_while(|&event| sender.send(event.clone()).is_ok())
+            .count();
+
+        self.ack(reader_id, sent)
     }
 
-    #[test]
-    fn test_read_write() {
-        let mut channel = EventChannel::with_capacity(14);
+    /// Start a transaction, which collects events and writes them all at
+    /// once when committed.
+    ///
+    /// This avoids writing only part of a batch if it turns out to be invalid
+    /// while assembling it.
+    pub fn begin(&mut self) -> WriteTxn<'_, E> {
+        WriteTxn::new(self)
+    }
 
-        let mut reader_id = channel.register_reader();
-        let mut reader_id_extra = channel.register_reader();
+    /// Write a single event into storage.
+    pub fn single_write(&mut self, event: E) {
+        self.storage.single_write(event);
+    }
 
-        channel.single_write(Test { id: 1 });
-        assert_eq!(
-            vec![Test { id: 1 }],
-            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
-        );
-        channel.single_write(Test { id: 2 });
-        assert_eq!(
-            vec![Test { id: 2 }],
-            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
-        );
+    /// Write a single event into storage, unless the channel stores an event
+    /// with the same key which no reader has read yet. That event is replaced
+    /// in place instead, and `true` is returned.
+    ///
+    /// This is useful for events where only the latest value matters, like
+    /// window resizes, so a burst of them doesn't flood the channel. An event
+    /// is only replaced if none of the readers has read it, so every reader
+    /// still sees the latest value. While writing is paused, the queued
+    /// events are searched instead.
+    pub fn write_coalesced<K, F>(&mut self, event: E, key: F) -> bool
+    where
+        K: PartialEq,
+        F: FnMut(&E) -> K,
+    {
+        self.storage.write_coalesced(event, key)
+    }
 
-        assert_eq!(
-            vec![Test { id: 1 }, Test { id: 2 }],
-            channel
-                .read(&mut reader_id_extra)
-                .cloned()
-                .collect::<Vec<_>>()
-        );
+    /// Write a single event into storage, unless the channel is full.
+    ///
+    /// The channel is full if writing would overwrite an event a reader
+    /// hasn't read yet, so it would have to grow. In that case, the event is
+    /// handed back, regardless of the `DropPolicy`. This is meant for
+    /// applying backpressure to a producer, instead of growing or dropping.
+    /// While writing is paused, the event is always queued.
+    pub fn try_single_write(&mut self, event: E) -> Result<(), E> {
+        self.storage.try_single_write(event)
+    }
 
-        channel.single_write(Test { id: 3 });
-        assert_eq!(
-            vec![Test { id: 3 }],
-            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
-        );
-        assert_eq!(
-            vec![Test { id: 3 }],
-            channel
-                .read(&mut reader_id_extra)
-                .cloned()
-                .collect::<Vec<_>>()
-        );
+    /// Write a single event into storage, returning a handle to look it up
+    /// with `get` later.
+    ///
+    /// Returns `None` if the event was dropped because the channel uses
+    /// `DropPolicy::DropNewest`, or queued because writing is paused.
+    pub fn single_write_handle(&mut self, event: E) -> Option<EventHandle> {
+        self.storage.single_write_handle(event)
     }
 
-    // There was previously a case where the tests worked but the example didn't, so
-    // the example was added as a test case.
-    #[test]
-    fn test_example() {
-        let mut channel = EventChannel::new();
+    /// Write a single event into storage, and call `on_evict` once the
+    /// channel no longer stores it, because newer events took its place.
+    ///
+    /// This lets a producer know when an event has aged out, e.g. to release
+    /// resources the event refers to. Callbacks run in the order the events
+    /// were written, during the write which evicts them. If the channel is
+    /// dropped before that, `on_evict` is dropped without being called.
+    ///
+    /// If the event isn't stored right away, because the channel uses
+    /// `DropPolicy::DropNewest` or writing is paused, `on_evict` is called
+    /// immediately.
+    pub fn single_write_with_eviction<F>(&mut self, event: E, on_evict: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.storage
+            .single_write_with_eviction(event, Box::new(on_evict));
+    }
 
-        channel.drain_vec_write(&mut vec![TestEvent { data: 1 }, TestEvent { data: 2 }]);
+    /// Returns the event `handle` refers to, or `None` if the channel no
+    /// longer stores it.
+    pub fn get(&self, handle: EventHandle) -> Option<&E> {
+        self.storage.get_by_handle(handle)
+    }
 
-        let mut reader_id = channel.register_reader();
+    /// Write an iterator of events into storage, returning the range of
+    /// sequence numbers they got, for looking them up with `slice` later.
+    ///
+    /// Like `iter_write`, the events are written at once. The range only
+    /// covers the events which were actually stored: it is shorter if some
+    /// were dropped because of the `DropPolicy` or an ingest filter, and
+    /// empty if writing is paused.
+    pub fn iter_write_returning<I>(&mut self, iter: I) -> EventRange
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.storage.iter_write_returning(iter)
+    }
 
-        // Should be empty, because reader was created after the write
-        assert_eq!(
-            Vec::<TestEvent>::default(),
-            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
-        );
+    /// Iterates over the events of `range` which the channel still stores,
+    /// e.g. to log or send the events of a write after the fact.
+    pub fn slice(&self, range: EventRange) -> EventIterator<'_, E> {
+        self.storage.iter_range(range)
+    }
 
-        // Should have data, as a second write was done
-        channel.single_write(TestEvent { data: 5 });
+    /// Register a new reader whose position is atomic, so reading with it
+    /// only needs shared references to both the channel and the reader.
+    ///
+    /// This lets several threads, e.g. the systems of a parallel scheduler,
+    /// read through the same reader at once; every event goes to exactly one
+    /// of the reads. Like a `ReaderId`, the reader only receives the events
+    /// written after its creation, and the channel keeps them until it has
+    /// read them.
+    pub fn register_atomic_reader(&mut self) -> AtomicReaderId<E> {
+        self.storage.new_atomic_reader_id()
+    }
 
-        assert_eq!(
+    /// Read the events written since the last read with `reader_id`.
+    ///
+    /// See `register_atomic_reader`.
+    pub fn read_atomic(&self, reader_id: &AtomicReaderId<E>) -> EventIterator<'_, E> {
+        self.storage.read_atomic(reader_id)
+    }
+
+    /// Read up to `max` of the events written since the last read with
+    /// `reader_id`, leaving the rest for the next read.
+    ///
+    /// Concurrent reads claim the events one batch at a time, see
+    /// `ReaderGroup`.
+    pub fn read_atomic_batch(
+        &self,
+        reader_id: &AtomicReaderId<E>,
+        max: usize,
+    ) -> EventIterator<'_, E> {
+        self.storage.read_atomic_batch(reader_id, max)
+    }
+
+    /// Returns how many events `reader_id` has not read yet.
+    pub fn atomic_reader_lag(&self, reader_id: &AtomicReaderId<E>) -> usize {
+        self.storage.atomic_reader_lag(reader_id)
+    }
+
+    /// Create a new `Cursor` pointing at the end of the channel.
+    ///
+    /// A cursor is a cheaper alternative to a `ReaderId` for code which keeps
+    /// track of many positions: it is not registered with, nor checked
+    /// against, the channel. In exchange, the channel won't keep events around
+    /// until the cursor has read them; see `Cursor` for details.
+    pub fn register_cursor(&self) -> Cursor {
+        self.storage.new_cursor()
+    }
+
+    /// Read any events that are still stored and have been written since the
+    /// last read with `cursor` (or the creation of the `Cursor`).
+    ///
+    /// Like `read`, this advances `cursor` to the end of the channel.
+    pub fn read_cursor(&self, cursor: &mut Cursor) -> EventIterator<'_, E> {
+        self.storage.read_cursor(cursor)
+    }
+
+    /// Returns how many events `cursor` has missed since its last read,
+    /// because they were overwritten before it could read them.
+    ///
+    /// This doesn't advance the cursor, so it can be used to decide whether
+    /// to resynchronize before reading.
+    pub fn lost_preview(&self, cursor: &Cursor) -> u64 {
+        self.storage.cursor_lost(cursor)
+    }
+
+    /// Returns the statistics collected since the channel was created or
+    /// `reset_stats` was last called.
+    pub fn stats(&self) -> Stats {
+        self.storage.stats()
+    }
+
+    /// Sets a function which gets called with the number of events whenever
+    /// events are lost, e.g. for exporting metrics.
+    ///
+    /// That is whenever a write drops events because of
+    /// `DropPolicy::DropNewest` and whenever a cursor read skips events
+    /// which were overwritten; readers registered with `register_reader`
+    /// only miss the former. The function is called during the read or
+    /// write, so reads from different threads may call it concurrently.
+    ///
+    /// Replaces the previously set function, if any.
+    pub fn set_loss_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(u64) + Send + Sync + 'static,
+    {
+        self.storage.set_loss_hook(Box::new(hook));
+    }
+
+    /// Sets a function which gets called with the id and lag of a reader
+    /// whenever a write makes the lag of the reader exceed `threshold`.
+    ///
+    /// The id is what `ReaderId::id` returns for the reader. The function is
+    /// only called when a reader crosses the threshold, not for every write
+    /// while it stays behind, so it is suitable for logging and alerting
+    /// about readers which got stuck. Use `set_loss_hook` to notice events
+    /// being lost instead.
+    ///
+    /// Replaces the previously set function, if any.
+    pub fn set_lag_alarm<F>(&mut self, threshold: usize, alarm: F)
+    where
+        F: FnMut(usize, usize) + Send + 'static,
+    {
+        self.storage.set_lag_alarm(threshold, Box::new(alarm));
+    }
+
+    /// Resets all statistics to zero.
+    pub fn reset_stats(&mut self) {
+        self.storage.reset_stats();
+    }
+
+    /// Returns the number of events stored in the channel, regardless of
+    /// which readers have read them.
+    pub fn len(&self) -> usize {
+        self.storage.iter_all().len()
+    }
+
+    /// Checks if the channel stores no events.
+    pub fn is_empty(&self) -> bool {
+        self.storage.iter_all().is_empty()
+    }
+
+    /// Returns the number of events the channel can store without growing.
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    /// Returns the most recent event, if the channel stores any.
+    pub fn latest(&self) -> Option<&E> {
+        self.storage.at_lag(0)
+    }
+
+    /// Returns the oldest event `reader_id` has not read yet, without
+    /// advancing it.
+    pub fn oldest_unread(&self, reader_id: &ReaderId<E>) -> Option<&E> {
+        self.storage.peek(reader_id).next()
+    }
+
+    /// Iterates over all events stored in the channel, from the oldest to the
+    /// most recent one, regardless of which readers have read them.
+    ///
+    /// The channel keeps events until it needs their space for new ones, so
+    /// this contains at least every event some reader hasn't read yet.
+    pub fn iter_all(&self) -> EventIterator<'_, E> {
+        self.storage.iter_all()
+    }
+
+    /// Like `iter_all`, but skips the `skip` oldest events and returns at most
+    /// `take` events.
+    ///
+    /// This is useful for paginated views of the stored events.
+    pub fn iter_window(&self, skip: usize, take: usize) -> EventIterator<'_, E> {
+        self.storage.iter_window(skip, take)
+    }
+
+    /// Binary searches the stored events for one with the key `key`, assuming
+    /// they were written in the order of the keys `f` extracts, e.g.
+    /// timestamps.
+    ///
+    /// Works like `slice::binary_search_by_key`. The returned index counts
+    /// from the oldest stored event, like `skip` of `iter_window` does.
+    pub fn binary_search_by_key<K, F>(&self, key: &K, f: F) -> Result<usize, usize>
+    where
+        K: Ord,
+        F: FnMut(&E) -> K,
+    {
+        self.storage.binary_search_by_key(key, f)
+    }
+
+    /// Iterates over the stored events with keys from `low` to `high`, both
+    /// inclusive, assuming they were written in the order of the keys `f`
+    /// extracts.
+    ///
+    /// The bounds are found with binary searches, see
+    /// `binary_search_by_key`.
+    pub fn range_by_key<K, F>(&self, low: &K, high: &K, mut f: F) -> EventIterator<'_, E>
+    where
+        K: Ord,
+        F: FnMut(&E) -> K,
+    {
+        let start = self.storage.partition_point(|e| f(e) < *low);
+        let end = self.storage.partition_point(|e| f(e) <= *high);
+
+        self.storage.iter_window(start, end.saturating_sub(start))
+    }
+
+    /// Returns the `index`th oldest stored event, or `None` if the channel
+    /// stores fewer events.
+    ///
+    /// The index counts like `skip` of `iter_window` does, so indices
+    /// returned by `binary_search_by_key` can be passed in directly.
+    pub fn get_stored(&self, index: usize) -> Option<&E> {
+        self.storage.get(index)
+    }
+
+    /// Like `get_stored`, but returns the event mutably.
+    ///
+    /// Readers which haven't read the event yet will see the modified one.
+    pub fn get_stored_mut(&mut self, index: usize) -> Option<&mut E> {
+        self.storage.get_mut(index)
+    }
+
+    /// Returns the stored event for which `f` returns the smallest key, e.g.
+    /// the pending event with the highest priority, without reading it.
+    ///
+    /// Of several events with the smallest key, the oldest one is returned.
+    /// Returns `None` if no events are stored.
+    pub fn min_by_key<K, F>(&self, f: F) -> Option<&E>
+    where
+        K: Ord,
+        F: FnMut(&&E) -> K,
+    {
+        self.iter_all().min_by_key(f)
+    }
+
+    /// Returns the stored event for which `f` returns the largest key.
+    ///
+    /// Of several events with the largest key, the most recent one is
+    /// returned. Returns `None` if no events are stored.
+    pub fn max_by_key<K, F>(&self, f: F) -> Option<&E>
+    where
+        K: Ord,
+        F: FnMut(&&E) -> K,
+    {
+        self.iter_all().max_by_key(f)
+    }
+
+    /// Returns the event which was written `lag` events before the most
+    /// recent one, so a `lag` of 0 returns the most recent event.
+    ///
+    /// Returns `None` if the channel no longer stores that event, or if fewer
+    /// events have been written.
+    pub fn at_lag(&self, lag: usize) -> Option<&E> {
+        self.storage.at_lag(lag)
+    }
+
+    /// Returns the events still stored in the channel, from the oldest to the
+    /// most recent one, as two slices.
+    ///
+    /// Like with `VecDeque::as_slices`, the second slice is only non-empty if
+    /// the events wrap around the end of the ring buffer. Call `compact`
+    /// first to get all of them in the first slice.
+    pub fn as_slices(&self) -> (&[E], &[E]) {
+        self.storage.as_slices()
+    }
+
+    /// Returns a pointer to the memory of the ring buffer, e.g. to hand it to
+    /// C code.
+    ///
+    /// The pointer is valid until the channel is written to or otherwise
+    /// modified. It points at the physical start of the ring buffer, not at
+    /// the oldest event: the events may wrap around the end, and slots which
+    /// haven't been written to yet are uninitialized. `as_slices` returns
+    /// where the stored events are, and `copy_retained_into` copies them in
+    /// order.
+    pub fn as_ptr(&self) -> *const E {
+        self.storage.as_ptr()
+    }
+
+    /// Copies the events still stored in the channel to `dst`, from the
+    /// oldest to the most recent one.
+    ///
+    /// ## Safety
+    ///
+    /// `dst` must be valid for writing `iter_all().len()` events and must
+    /// not overlap with the memory of the channel.
+    pub unsafe fn copy_retained_into(&self, dst: *mut E)
+    where
+        E: Copy,
+    {
+        let (first, second) = self.as_slices();
+        std::ptr::copy_nonoverlapping(first.as_ptr(), dst, first.len());
+        std::ptr::copy_nonoverlapping(second.as_ptr(), dst.add(first.len()), second.len());
+    }
+
+    /// Copies the events still stored in the channel into a `VecDeque`,
+    /// from the oldest to the most recent one.
+    pub fn to_vec_deque(&self) -> VecDeque<E>
+    where
+        E: Clone,
+    {
+        let (first, second) = self.as_slices();
+        let mut events = VecDeque::with_capacity(first.len() + second.len());
+        events.extend(first.iter().cloned());
+        events.extend(second.iter().cloned());
+
+        events
+    }
+
+    /// Reduces the capacity of the channel to `size`, e.g. after a burst of
+    /// events made it grow, keeping the most recent events.
+    ///
+    /// Readers never lose events because of this: the capacity stays large
+    /// enough for all events the reader furthest behind has pending, and at
+    /// least 2. Does nothing if the capacity is `size` or less already.
+    pub fn shrink_to(&mut self, size: usize) {
+        self.storage.shrink_to(size);
+    }
+
+    /// Reduces the capacity of the channel as far as possible, i.e. to the
+    /// number of events the reader furthest behind has pending.
+    ///
+    /// See `shrink_to`.
+    pub fn shrink_to_fit(&mut self) {
+        self.storage.shrink_to(0);
+    }
+
+    /// Changes the capacity of the channel to `size`, e.g. once it turns out
+    /// to be too small for the usual number of events.
+    ///
+    /// All readers keep their position, so they still read every event they
+    /// have pending. Growing gives the channel exactly `size` slots, while
+    /// shrinking works like `shrink_to`, so the capacity may stay larger.
+    pub fn resize(&mut self, size: usize) {
+        match size > self.storage.capacity() {
+            true => self.storage.grow_to(size),
+            false => self.storage.shrink_to(size),
+        }
+    }
+
+    /// Swaps the events stored in this channel with the ones of `other`,
+    /// without moving any of them.
+    ///
+    /// The sequence numbers of the events are swapped along with them, see
+    /// `next_sequence`. Readers don't get to see the swapped in events: the
+    /// readers of both channels are moved to the end, like newly registered
+    /// ones. Everything else, like the `DropPolicy` and the `stats`, stays
+    /// with the channel.
+    pub fn swap_data(&mut self, other: &mut EventChannel<E>) {
+        self.storage.swap_data(&mut other.storage);
+    }
+
+    /// Drops the stored events with sequence numbers below `sequence`, even
+    /// if readers haven't read them yet, returning how many were dropped.
+    ///
+    /// This is meant for events which are useless once they are too old,
+    /// like input or network events: readers skip the expired events, so
+    /// they don't handle them late. Ticks or times can be mapped to sequence
+    /// numbers by recording `next_sequence` once per tick. Expired events are
+    /// counted in `Stats::events_expired`, separately from lost ones.
+    pub fn expire_before(&mut self, sequence: u64) -> usize {
+        self.storage.expire_before(sequence)
+    }
+
+    /// Drops the stored events which every reader has read already,
+    /// returning how many those were.
+    ///
+    /// The channel normally keeps events until their slot is needed for a
+    /// new one, which keeps whatever they own, like file handles or `Arc`s,
+    /// alive for an unpredictable time. After this, `iter_all` only returns
+    /// events some reader has pending; without readers, all events are
+    /// dropped. Cursors count the dropped events as lost.
+    pub fn clear_consumed(&mut self) -> usize {
+        self.storage.clear_consumed()
+    }
+
+    /// Moves the events in the ring buffer so the oldest one is stored
+    /// first, making them contiguous in memory.
+    ///
+    /// The events and readers are unaffected otherwise: every reader still
+    /// receives the same events on the next `read`.
+    pub fn compact(&mut self) {
+        self.storage.compact();
+    }
+
+    /// Splits the channel into a handle for writing and a handle for reading.
+    ///
+    /// This is useful to give different parts of an application only the
+    /// capability they need. Existing readers can still be used with the
+    /// `ReaderFactory`.
+    ///
+    /// The channel is taken by value: the handles outlive any borrow of it,
+    /// and taking it out of a `&mut self` would have to leave another channel
+    /// behind, which the readers registered before don't belong to.
+    ///
+    /// Both handles share the channel through an `Rc<RefCell<_>>`, assuming
+    /// they are only used from a single thread. Neither is `Send` or `Sync`;
+    /// use `SyncEventChannel` to share a channel between threads instead.
+    pub fn split(self) -> (Writer<E>, ReaderFactory<E>) {
+        split::split(self)
+    }
+
+    /// Read any events that have been written to storage since the last read
+    /// with `reader_id` (or the creation of the `ReaderId`, if it hasn't read
+    /// yet).
+    ///
+    /// Note that this will advance the position of the reader regardless of
+    /// what you do with the iterator. In other words, calling `read`
+    /// without iterating the result won't preserve the events returned. You
+    /// need to iterate all the events as soon as you got them from this
+    /// method. This behavior is equivalent to e.g. `Vec::drain`.
+    pub fn read(&self, reader_id: &mut ReaderId<E>) -> EventIterator<'_, E> {
+        self.storage.read(reader_id)
+    }
+
+    /// Returns the events `read` would return, without advancing the position
+    /// of the reader.
+    ///
+    /// Together with `ack`, this allows processing events with at-least-once
+    /// semantics: events are only marked as read once they have been handled.
+    pub fn peek(&self, reader_id: &ReaderId<E>) -> EventIterator<'_, E> {
+        self.storage.peek(reader_id)
+    }
+
+    /// Returns a view of the events `reader_id` would receive if it would
+    /// `read` now, without advancing it.
+    ///
+    /// Unlike `peek`, the view can be iterated any number of times, e.g. to
+    /// render the pending events before deciding to consume them.
+    pub fn snapshot_reader(&self, reader_id: &ReaderId<E>) -> SnapshotReader<'_, E> {
+        self.storage.snapshot(reader_id)
+    }
+
+    /// Marks the next `count` events as read by `reader_id`, so they are not
+    /// returned by future reads.
+    ///
+    /// Returns the number of events which were acknowledged, which is less
+    /// than `count` if the reader didn't have that many events pending.
+    pub fn ack(&self, reader_id: &mut ReaderId<E>, count: usize) -> usize {
+        self.storage.ack(reader_id, count)
+    }
+
+    /// Returns the events `reader_id` has pending without advancing it, like
+    /// `peek`, along with a token to pass to `commit` once they have been
+    /// processed.
+    ///
+    /// This is meant for at-least-once processing: if processing fails, the
+    /// token is simply dropped and the events are returned again.
+    pub fn read_uncommitted(&self, reader_id: &ReaderId<E>) -> (EventIterator<'_, E>, CommitToken) {
+        self.storage.read_uncommitted(reader_id)
+    }
+
+    /// Advances `reader_id` past the events returned along with `token` by
+    /// `read_uncommitted`, returning how many that were.
+    ///
+    /// Events written in the meantime remain pending. Fails if `token` was
+    /// created for another reader, or if the reader has been moved since,
+    /// e.g. by a read, an `ack` or by skipping events because of its maximum
+    /// age; then the reader isn't changed.
+    pub fn commit(
+        &self,
+        reader_id: &mut ReaderId<E>,
+        token: CommitToken,
+    ) -> Result<usize, StaleCommit> {
+        self.storage.commit(reader_id, token)
+    }
+
+    /// Returns the current position of `reader_id`, so it can be moved back
+    /// there with `restore` to read the events after it again, e.g. after a
+    /// rollback.
+    pub fn checkpoint(&self, reader_id: &ReaderId<E>) -> ReaderCheckpoint {
+        self.storage.checkpoint(reader_id)
+    }
+
+    /// Moves `reader_id` to `checkpoint`, returning how many events it has
+    /// pending afterwards.
+    ///
+    /// Checkpoints don't keep events in the channel: once the first event
+    /// after the checkpoint has been overwritten, or if the checkpoint was
+    /// created for another reader, this fails and the reader isn't changed.
+    /// Restoring a checkpoint created before the latest reads of a reader
+    /// makes it read those events again; that is the usual case.
+    pub fn restore(
+        &mut self,
+        reader_id: &mut ReaderId<E>,
+        checkpoint: ReaderCheckpoint,
+    ) -> Result<usize, CheckpointExpired> {
+        self.storage.restore(reader_id, checkpoint)
+    }
+
+    /// Calls `f` on every event `reader_id` has pending, stopping at the
+    /// first one for which it returns an error.
+    ///
+    /// The reader is only advanced past the events which were processed
+    /// successfully, so the failed event is the first one of the next read.
+    pub fn read_try_for_each<F, Err>(
+        &self,
+        reader_id: &mut ReaderId<E>,
+        mut f: F,
+    ) -> Result<(), Err>
+    where
+        F: FnMut(&E) -> Result<(), Err>,
+    {
+        let mut processed = 0;
+        let result = self.storage.peek(reader_id).try_for_each(|event| {
+            f(event)?;
+            processed += 1;
+
+            Ok(())
+        });
+        self.storage.ack(reader_id, processed);
+
+        result
+    }
+
+    /// Apply `f` to every event `read` would return, modifying them in place,
+    /// and advance the reader past them. Returns the number of events.
+    ///
+    /// Note that the modification is visible to all readers which haven't
+    /// read the events yet.
+    pub fn read_transform<F>(&mut self, reader_id: &mut ReaderId<E>, f: F) -> usize
+    where
+        F: FnMut(&mut E),
+    {
+        self.storage.read_transform(reader_id, f)
+    }
+
+    /// Read the same events as `read` would, moving them out of the channel.
+    ///
+    /// Each event is replaced by its default value as the returned iterator
+    /// reaches it; events the iterator doesn't reach stay in the channel.
+    /// The reader is advanced past all events right away.
+    ///
+    /// Since other readers would only see the default values afterwards,
+    /// this should only be used if `reader_id` is the only reader of the
+    /// channel.
+    pub fn drain_read<'a>(
+        &'a mut self,
+        reader_id: &mut ReaderId<E>,
+    ) -> impl Iterator<Item = E> + 'a
+    where
+        E: Default,
+    {
+        self.storage.drain_read(reader_id)
+    }
+
+    /// Read the same events as `read` would, but only return the ones
+    /// matching `pred`.
+    ///
+    /// The reader is advanced past all events, including the ones which
+    /// don't match.
+    pub fn read_filter<'a, F>(
+        &'a self,
+        reader_id: &mut ReaderId<E>,
+        mut pred: F,
+    ) -> impl Iterator<Item = &'a E> + 'a
+    where
+        F: FnMut(&E) -> bool + 'a,
+    {
+        self.storage.read(reader_id).filter(move |e| pred(e))
+    }
+
+    /// Read the same events as `read` would, but return what `f` derives from
+    /// them, without collecting them first.
+    pub fn read_map<'a, U, F>(
+        &'a self,
+        reader_id: &mut ReaderId<E>,
+        f: F,
+    ) -> impl Iterator<Item = U> + 'a
+    where
+        F: FnMut(&E) -> U + 'a,
+    {
+        self.storage.read(reader_id).map(f)
+    }
+
+    /// Read the events pending for any of `readers` at once.
+    ///
+    /// All readers are advanced past their pending events, like with `read`.
+    /// Since readers only ever lag behind by the most recent events, the
+    /// events pending for the reader furthest behind include those of all
+    /// others; they are returned exactly once, from the oldest to the most
+    /// recent one.
+    pub fn read_coalesced(&self, readers: &mut [ReaderId<E>]) -> EventIterator<'_, E> {
+        self.storage.read_coalesced(readers)
+    }
+
+    /// Read the same events as `read` would, returning `None` instead of an
+    /// empty iterator if there are none.
+    pub fn read_some(&self, reader_id: &mut ReaderId<E>) -> Option<EventIterator<'_, E>> {
+        match self.reader_caught_up(reader_id) {
+            true => None,
+            false => Some(self.read(reader_id)),
+        }
+    }
+
+    /// Read the same events as `read` would, along with their sequence
+    /// numbers.
+    ///
+    /// See `next_sequence` for how events are numbered.
+    pub fn read_seq(&self, reader_id: &mut ReaderId<E>) -> impl Iterator<Item = (u64, &E)> {
+        let first = self.storage.written() - self.storage.reader_lag(reader_id) as u64;
+
+        (first..).zip(self.storage.read(reader_id))
+    }
+
+    /// Read the same events as `read` would, along with their age: the
+    /// number of events written after them.
+    ///
+    /// The most recent event has an age of 0.
+    pub fn read_with_age(&self, reader_id: &mut ReaderId<E>) -> impl Iterator<Item = (&E, usize)> {
+        let num = self.reader_lag(reader_id);
+
+        self.storage.read(reader_id).zip((0..num).rev())
+    }
+
+    /// Read the same events as `read` would, along with the positions of the
+    /// first and the last of them within the ring buffer.
+    ///
+    /// Both positions are inclusive. If the events wrap around the end of
+    /// the ring buffer, the first position is greater than the last one.
+    /// There are no positions if there are no events.
+    pub fn read_with_range(
+        &self,
+        reader_id: &mut ReaderId<E>,
+    ) -> (EventIterator<'_, E>, Option<(usize, usize)>) {
+        let events = self.storage.read(reader_id);
+        let range = events.physical_range();
+
+        (events, range)
+    }
+
+    /// Read the same events as `read` would, as the two slices of the ring
+    /// buffer they are stored in.
+    ///
+    /// The second slice is only non-empty if the events wrap around the end
+    /// of the ring buffer. Nothing is cloned, so this is the cheapest way to
+    /// copy the events elsewhere in bulk. See also `read_contiguous`.
+    pub fn read_slices(&self, reader_id: &mut ReaderId<E>) -> (&[E], &[E]) {
+        self.storage.read(reader_id).as_slices()
+    }
+
+    /// Read the same events as `read` would, as a single slice.
+    ///
+    /// The events are borrowed from the channel if they are stored
+    /// contiguously, which is the case unless they wrap around the end of the
+    /// ring buffer. Otherwise, they are cloned into a new vector.
+    pub fn read_contiguous(&self, reader_id: &mut ReaderId<E>) -> Cow<'_, [E]>
+    where
+        E: Clone,
+    {
+        match self.storage.read(reader_id).as_slices() {
+            (events, []) => Cow::Borrowed(events),
+            (first, second) => Cow::Owned([first, second].concat()),
+        }
+    }
+
+    /// Read the same events as `read` would, grouped by the key `key`
+    /// returns for them.
+    ///
+    /// Within each group, the events are ordered from the oldest to the most
+    /// recent one.
+    pub fn read_grouped<K, F>(&self, reader_id: &mut ReaderId<E>, mut key: F) -> HashMap<K, Vec<&E>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&E) -> K,
+    {
+        let mut groups = HashMap::new();
+        for event in self.storage.read(reader_id) {
+            groups.entry(key(event)).or_insert_with(Vec::new).push(event);
+        }
+
+        groups
+    }
+
+    /// Read the same events as `read` would and collect references to them.
+    ///
+    /// This doesn't require the events to implement `Clone`, unlike the
+    /// common `read(..).cloned().collect()`.
+    pub fn read_refs(&self, reader_id: &mut ReaderId<E>) -> Vec<&E> {
+        self.storage.read(reader_id).collect()
+    }
+
+    /// Read the same events as `read` would and collect references to them,
+    /// leaving out events which are equal to the one before them.
+    ///
+    /// Like `Vec::dedup`, this only collapses consecutive duplicates. The
+    /// reader is still advanced past all events; the first pending event is
+    /// never compared with the ones read before.
+    pub fn read_dedup(&self, reader_id: &mut ReaderId<E>) -> Vec<&E>
+    where
+        E: PartialEq,
+    {
+        let mut events = self.read_refs(reader_id);
+        events.dedup();
+
+        events
+    }
+
+    /// Read at most `max` of the events `read` would return, returning them
+    /// along with the number of events which remain pending.
+    ///
+    /// This allows spreading the handling of a burst of events over several
+    /// frames, e.g. to stay within a time budget. The remaining events are
+    /// returned by the next reads.
+    pub fn read_batch(
+        &self,
+        reader_id: &mut ReaderId<E>,
+        max: usize,
+    ) -> (EventIterator<'_, E>, usize) {
+        self.storage.read_batch(reader_id, max)
+    }
+
+    /// Read the same events as `read` would and append clones of them to
+    /// `out`, returning how many were appended.
+    ///
+    /// This works with any collection implementing `Extend`, so a buffer can
+    /// be reused across reads without allocating for each of them.
+    pub fn read_extend<C>(&self, reader_id: &mut ReaderId<E>, out: &mut C) -> usize
+    where
+        E: Clone,
+        C: Extend<E>,
+    {
+        let events = self.storage.read(reader_id);
+        let len = events.len();
+        out.extend(events.cloned());
+
+        len
+    }
+
+    /// Read the same events as `read` would, but ordered from the most recent
+    /// to the oldest one.
+    ///
+    /// Like `read`, this advances the position of the reader past all
+    /// returned events.
+    pub fn read_newest_first(&self, reader_id: &mut ReaderId<E>) -> Vec<&E> {
+        self.storage.read(reader_id).rev().collect()
+    }
+}
+
+/// Creates a channel which stores `events` as if they had been written to
+/// it, with a capacity of exactly the number of events (but at least 2).
+///
+/// Since there are no readers yet, none will receive these events, but they
+/// are returned by `iter_all` and the other methods inspecting the stored
+/// events.
+impl<E> From<Vec<E>> for EventChannel<E>
+where
+    E: Event,
+{
+    fn from(events: Vec<E>) -> Self {
+        let mut channel = EventChannel::with_capacity(events.len().max(2));
+        channel.iter_write(events);
+
+        channel
+    }
+}
+
+/// Consumes the channel, yielding the events it still stores from the oldest
+/// to the most recent one.
+impl<E> IntoIterator for EventChannel<E>
+where
+    E: Event,
+{
+    type Item = E;
+    type IntoIter = std::vec::IntoIter<E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.storage.into_retained().into_iter()
+    }
+}
+
+/// Iterates over the events the channel stores, like `iter_all`.
+impl<'a, E> IntoIterator for &'a EventChannel<E>
+where
+    E: Event,
+{
+    type Item = &'a E;
+    type IntoIter = EventIterator<'a, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{testing::EventChannelExt, *};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Test {
+        pub id: u32,
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut channel = EventChannel::with_capacity(10);
+
+        let mut reader0 = channel.register_reader();
+        let mut reader1 = channel.register_reader();
+
+        channel.iter_write(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let data = channel.read(&mut reader0).cloned().collect::<Vec<_>>();
+        assert_eq!(data, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        channel.iter_write(vec![9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]);
+
+        let data = channel.read(&mut reader0).cloned().collect::<Vec<_>>();
+        assert_eq!(
+            data,
+            vec![9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22]
+        );
+
+        for i in 23..10_000 {
+            channel.single_write(i);
+        }
+
+        let data = channel.read(&mut reader1).cloned().collect::<Vec<_>>();
+        assert_eq!(data, (1..10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_read_write() {
+        let mut channel = EventChannel::with_capacity(14);
+
+        let mut reader_id = channel.register_reader();
+        let mut reader_id_extra = channel.register_reader();
+
+        channel.single_write(Test { id: 1 });
+        channel.assert_reads(&mut reader_id, &[Test { id: 1 }]);
+        channel.single_write(Test { id: 2 });
+        channel.assert_reads(&mut reader_id, &[Test { id: 2 }]);
+
+        channel.assert_reads(&mut reader_id_extra, &[Test { id: 1 }, Test { id: 2 }]);
+
+        channel.single_write(Test { id: 3 });
+        channel.assert_reads(&mut reader_id, &[Test { id: 3 }]);
+        channel.assert_reads(&mut reader_id_extra, &[Test { id: 3 }]);
+    }
+
+    // There was previously a case where the tests worked but the example didn't, so
+    // the example was added as a test case.
+    #[test]
+    fn test_example() {
+        let mut channel = EventChannel::new();
+
+        channel.drain_vec_write(&mut vec![TestEvent { data: 1 }, TestEvent { data: 2 }]);
+
+        let mut reader_id = channel.register_reader();
+
+        // Should be empty, because reader was created after the write
+        assert_eq!(
+            Vec::<TestEvent>::default(),
+            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
+        );
+
+        // Should have data, as a second write was done
+        channel.single_write(TestEvent { data: 5 });
+
+        assert_eq!(
             vec![TestEvent { data: 5 }],
             channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
         );
 
-        // We can also just send in an iterator.
-        channel.iter_write(
-            [TestEvent { data: 8 }, TestEvent { data: 9 }]
-                .iter()
-                .cloned(),
+        // We can also just send in an iterator.
+        channel.iter_write(
+            [TestEvent { data: 8 }, TestEvent { data: 9 }]
+                .iter()
+                .cloned(),
+        );
+
+        assert_eq!(
+            vec![TestEvent { data: 8 }, TestEvent { data: 9 }],
+            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn read_duplicated() {
+        let mut channel = EventChannel::new();
+
+        // This one shouldn't be seen by either channel
+        channel.single_write(TestEvent { data: 0 });
+
+        let mut reader1 = channel.register_reader();
+
+        // This one should be seen by both
+        channel.single_write(TestEvent { data: 1 });
+
+        let mut reader2 = channel.duplicate_reader(&reader1);
+
+        channel.assert_reads(&mut reader1, &[TestEvent { data: 1 }]);
+
+        // This one should also be seen by both
+        channel.write_events(vec![TestEvent { data: 2 }]);
+
+        channel.assert_reads(&mut reader1, &[TestEvent { data: 2 }]);
+        channel.assert_reads(&mut reader2, &[TestEvent { data: 1 }, TestEvent { data: 2 }]);
+    }
+
+    #[test]
+    fn read_cursor() {
+        let mut channel = EventChannel::with_capacity(4);
+
+        let mut reader = channel.register_reader();
+        let mut cursor = channel.register_cursor();
+
+        channel.iter_write(0..3);
+        assert_eq!(
+            channel.read_cursor(&mut cursor).collect::<Vec<_>>(),
+            channel.read(&mut reader).collect::<Vec<_>>()
+        );
+
+        // The reader keeps the events around for the cursor, too
+        channel.iter_write(3..20);
+        assert_eq!(
+            channel.read_cursor(&mut cursor).collect::<Vec<_>>(),
+            channel.read(&mut reader).collect::<Vec<_>>()
+        );
+        assert_eq!(channel.read_cursor(&mut cursor).len(), 0);
+    }
+
+    #[test]
+    fn read_cursor_overwritten() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut cursor = channel.register_cursor();
+
+        channel.iter_write(0..10);
+        assert_eq!(
+            channel.read_cursor(&mut cursor).cloned().collect::<Vec<_>>(),
+            vec![6, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn read_newest_first() {
+        let mut channel = EventChannel::with_capacity(4);
+
+        let mut reader = channel.register_reader();
+        let mut reader_rev = channel.register_reader();
+
+        for i in 0..3 {
+            channel.iter_write(i * 3..i * 3 + 3);
+
+            let mut events = channel.read(&mut reader).collect::<Vec<_>>();
+            events.reverse();
+            assert_eq!(channel.read_newest_first(&mut reader_rev), events);
+        }
+    }
+
+    #[test]
+    fn append_clamped() {
+        let mut channel = EventChannel::with_capacity(8);
+        let mut reader = channel.register_reader();
+
+        channel.append_clamped(&(0..16).collect::<Vec<_>>());
+        assert_eq!(
+            channel.read(&mut reader).cloned().collect::<Vec<_>>(),
+            (8..16).collect::<Vec<_>>()
+        );
+
+        channel.append_clamped(&[16, 17]);
+        assert_eq!(
+            channel.read(&mut reader).cloned().collect::<Vec<_>>(),
+            vec![16, 17]
+        );
+        // A lagging reader keeps its pending events, growing the buffer
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..2);
+        channel.append_clamped(&(10..18).collect::<Vec<_>>());
+        assert_eq!(channel.capacity(), 8);
+        channel.assert_reads(&mut reader, &[0, 1, 14, 15, 16, 17]);
+    }
+
+    #[test]
+    fn reader_lag() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert_eq!(channel.slowest_reader_lag(), 0);
+
+        let mut reader0 = channel.register_reader();
+        channel.iter_write(0..3);
+        let mut reader1 = channel.register_reader();
+        channel.iter_write(3..8);
+        channel.read(&mut reader1);
+        let reader2 = channel.register_reader();
+        channel.single_write(8);
+
+        assert_eq!(channel.reader_lag(&reader0), 9);
+        assert_eq!(channel.reader_lag(&reader1), 1);
+        assert_eq!(channel.reader_lag(&reader2), 1);
+        assert_eq!(channel.slowest_reader_lag(), 9);
+
+        channel.read(&mut reader0);
+        assert_eq!(channel.reader_lag(&reader0), 0);
+        assert_eq!(channel.slowest_reader_lag(), 1);
+
+        drop((reader1, reader2));
+        assert_eq!(channel.slowest_reader_lag(), 0);
+    }
+
+    #[test]
+    fn reader_positions() {
+        let mut channel = EventChannel::with_capacity(4);
+
+        let mut reader0 = channel.register_reader();
+        let mut reader1 = channel.register_reader();
+        let reader2 = channel.register_reader();
+
+        channel.iter_write(0..2);
+        channel.read(&mut reader0);
+        channel.single_write(2);
+        channel.read(&mut reader1);
+        channel.single_write(3);
+
+        assert_eq!(
+            channel.reader_positions().collect::<Vec<_>>(),
+            vec![(reader0.id(), 2), (reader1.id(), 1), (reader2.id(), 4)]
+        );
+
+        drop(reader1);
+        assert_eq!(
+            channel.reader_positions().collect::<Vec<_>>(),
+            vec![(reader0.id(), 2), (reader2.id(), 4)]
+        );
+    }
+
+    #[test]
+    fn empty_write() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        let empty_writes = Arc::new(AtomicUsize::new(0));
+        channel.set_empty_write_hook({
+            let empty_writes = empty_writes.clone();
+            move || {
+                empty_writes.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        channel.iter_write(Vec::new());
+        channel.drain_vec_write(&mut vec![]);
+        assert_eq!(empty_writes.load(Ordering::Relaxed), 2);
+        assert_eq!(channel.stats().writes, 0);
+        assert_eq!(channel.read(&mut reader).len(), 0);
+
+        channel.single_write(1);
+        assert_eq!(empty_writes.load(Ordering::Relaxed), 2);
+        assert_eq!(channel.stats().writes, 1);
+    }
+
+    #[test]
+    fn peek_ack() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write(0..4);
+        assert_eq!(
+            channel.peek(&reader).cloned().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(channel.ack(&mut reader, 1), 1);
+        assert_eq!(
+            channel.peek(&reader).cloned().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        // Events acknowledged so far may be overwritten
+        channel.iter_write(4..5);
+        assert_eq!(channel.ack(&mut reader, 2), 2);
+        assert_eq!(
+            channel.read(&mut reader).cloned().collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(channel.ack(&mut reader, 2), 0);
+    }
+
+    #[test]
+    fn next_sequence() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert_eq!(channel.next_sequence(), 0);
+
+        channel.single_write(0);
+        assert_eq!(channel.next_sequence(), 1);
+        channel.iter_write(1..10);
+        assert_eq!(channel.next_sequence(), 10);
+        channel.iter_write(Vec::new());
+        assert_eq!(channel.next_sequence(), 10);
+    }
+
+    #[test]
+    fn clone_reader_at_head() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+
+        let mut clone = channel.clone_reader_at_head(&reader);
+        assert_eq!(channel.reader_lag(&clone), 0);
+        channel.single_write(3);
+        channel.assert_reads(&mut clone, &[3]);
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clone_reader_at_head_foreign() {
+        let mut channel = EventChannel::<i32>::new();
+        let foreign = EventChannel::new().register_reader();
+
+        channel.clone_reader_at_head(&foreign);
+    }
+
+    #[test]
+    fn read_transform() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut other = channel.register_reader();
+
+        channel.iter_write(0..3);
+        channel.assert_reads(&mut other, &[0, 1, 2]);
+        channel.iter_write(3..6);
+
+        assert_eq!(channel.read_transform(&mut reader, |e| *e *= 10), 6);
+        assert_eq!(channel.read(&mut reader).len(), 0);
+        assert_eq!(channel.read_transform(&mut reader, |e| *e *= 10), 0);
+        channel.assert_reads(&mut other, &[30, 40, 50]);
+    }
+
+    #[test]
+    fn reader_id_as_key() {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashMap},
+            hash::{Hash, Hasher},
+        };
+
+        let hash = |reader: &ReaderId<i32>| {
+            let mut hasher = DefaultHasher::new();
+            reader.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let second = channel.register_reader();
+        // Same id, but a different channel
+        let foreign = EventChannel::with_capacity(4).register_reader();
+        assert_eq!(reader, reader);
+        assert_ne!(reader, second);
+        assert_ne!(reader, foreign);
+
+        let before = hash(&reader);
+        channel.iter_write(0..3);
+        channel.read(&mut reader);
+        assert_eq!(hash(&reader), before);
+
+        let mut names = HashMap::new();
+        names.insert(second, "second");
+        names.insert(foreign, "foreign");
+        names.insert(reader, "reader");
+        assert_eq!(names.len(), 3);
+    }
+
+    #[test]
+    fn lost_preview() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut cursor = channel.register_cursor();
+
+        channel.iter_write(0..4);
+        assert_eq!(channel.lost_preview(&cursor), 0);
+        channel.iter_write(4..14);
+        assert_eq!(channel.lost_preview(&cursor), 10);
+        assert_eq!(channel.lost_preview(&cursor), 10);
+
+        channel.read_cursor(&mut cursor);
+        assert_eq!(channel.lost_preview(&cursor), 0);
+    }
+
+    #[test]
+    fn read_refs() {
+        #[derive(Debug, PartialEq)]
+        struct NotClone(u32);
+
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write((0..6).map(NotClone));
+        assert_eq!(
+            channel.read_refs(&mut reader),
+            (0..6).map(NotClone).collect::<Vec<_>>().iter().collect::<Vec<_>>()
+        );
+        assert!(channel.read_refs(&mut reader).is_empty());
+    }
+
+    #[test]
+    fn read_slices() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+        assert_eq!(channel.read_slices(&mut reader), (&[0, 1, 2][..], &[][..]));
+
+        channel.iter_write(3..6);
+        assert_eq!(channel.read_slices(&mut reader), (&[3][..], &[4, 5][..]));
+        assert_eq!(channel.read_slices(&mut reader), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn read_map() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(vec![(1, "a"), (2, "b"), (3, "c")]);
+
+        let ids: Vec<_> = channel.read_map(&mut reader, |&(id, _)| id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+        assert_eq!(channel.read_map(&mut reader, |&(id, _)| id).count(), 0);
+    }
+
+    #[test]
+    fn read_dedup() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(vec![1, 1, 2, 2, 2, 1, 3, 3]);
+        assert_eq!(channel.read_dedup(&mut reader), [&1, &2, &1, &3]);
+        assert!(channel.read_dedup(&mut reader).is_empty());
+
+        channel.iter_write(vec![3, 3]);
+        assert_eq!(channel.read_dedup(&mut reader), [&3]);
+        assert_eq!(channel.reader_lag(&reader), 0);
+    }
+
+    #[test]
+    fn read_extend() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut vec = vec![0];
+        let mut deque = VecDeque::new();
+
+        channel.iter_write(1..4);
+        assert_eq!(channel.read_extend(&mut reader, &mut vec), 3);
+        assert_eq!(channel.read_extend(&mut reader, &mut vec), 0);
+        channel.iter_write(4..9);
+        assert_eq!(channel.read_extend(&mut reader, &mut deque), 5);
+
+        assert_eq!(vec, [0, 1, 2, 3]);
+        assert_eq!(deque, [4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn read_batch() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..7);
+
+        let (events, remaining) = channel.read_batch(&mut reader, 3);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(remaining, 4);
+        let (events, remaining) = channel.read_batch(&mut reader, 3);
+        assert_eq!((events.len(), remaining), (3, 1));
+        channel.single_write(7);
+        let (events, remaining) = channel.read_batch(&mut reader, 3);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [6, 7]);
+        assert_eq!(remaining, 0);
+
+        let (events, remaining) = channel.read_batch(&mut reader, 3);
+        assert_eq!((events.len(), remaining), (0, 0));
+        channel.iter_write(8..10);
+        assert_eq!(channel.read_batch(&mut reader, 0).1, 2);
+        channel.assert_reads(&mut reader, &[8, 9]);
+        assert_eq!(channel.stats().events_read, 8 + 2);
+    }
+
+    #[test]
+    fn drain_read() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+        let mut drain = channel.drain_read(&mut reader);
+        assert_eq!(drain.next().as_deref(), Some("a"));
+        assert_eq!(drain.next().as_deref(), Some("b"));
+        drop(drain);
+
+        assert_eq!(channel.reader_lag(&reader), 0);
+        assert_eq!(channel.iter_all().collect::<Vec<_>>(), ["", "", "c"]);
+
+        channel.single_write("d".to_string());
+        assert_eq!(channel.drain_read(&mut reader).collect::<Vec<_>>(), ["d"]);
+        assert_eq!(channel.drain_read(&mut reader).count(), 0);
+    }
+
+    #[test]
+    fn recommend_capacity() {
+        use super::recommend_capacity;
+
+        assert_eq!(recommend_capacity(1000.0, Duration::from_millis(250)), 250);
+        assert_eq!(recommend_capacity(30.0, Duration::from_millis(1010)), 31);
+        assert_eq!(recommend_capacity(0.5, Duration::from_secs(1)), 2);
+        assert_eq!(recommend_capacity(0.0, Duration::from_secs(10)), 2);
+        assert_eq!(recommend_capacity(-5.0, Duration::from_secs(1)), 2);
+        assert_eq!(recommend_capacity(f64::NAN, Duration::from_secs(1)), 2);
+        assert_eq!(recommend_capacity(f64::INFINITY, Duration::from_secs(1)), usize::MAX);
+    }
+
+    #[test]
+    fn resync_all_readers() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.resync_all_readers();
+        channel.iter_write(0..6);
+        let mut first = channel.register_reader();
+        let mut second = channel.register_reader();
+
+        channel.resync_all_readers();
+        channel.assert_reads(&mut first, &[2, 3, 4, 5]);
+        // `second` still has all stored events pending, so the channel grows
+        channel.single_write(6);
+        assert_eq!(channel.storage.capacity(), 8);
+        channel.read(&mut second);
+        channel.assert_reads(&mut first, &[6]);
+
+        channel.resync_all_readers();
+        channel.iter_write(7..9);
+        channel.assert_reads(&mut first, &[2, 3, 4, 5, 6, 7, 8]);
+        channel.assert_reads(&mut second, &[2, 3, 4, 5, 6, 7, 8]);
+
+        channel.iter_write(9..11);
+        channel.resync_all_readers();
+        channel.assert_reads(&mut first, &[3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn purge_reader() {
+        let mut channel = EventChannel::<i32>::new();
+        let first = channel.register_reader();
+
+        for _ in 0..100 {
+            let reader = channel.register_reader();
+            assert_eq!(reader.id(), 1);
+            channel.purge_reader(reader);
+            assert_eq!(channel.reader_positions().count(), 1);
+        }
+
+        channel.purge_reader(first);
+        assert!(!channel.would_write());
+    }
+
+    #[test]
+    fn binary_search_by_key() {
+        let mut channel = EventChannel::with_capacity(8);
+        assert_eq!(channel.binary_search_by_key(&0, |&(t, _)| t), Err(0));
+
+        // Timestamps 20, 30, .., 90 remain, wrapping around the end
+        channel.iter_write((1..10).map(|t| (t * 10, t)));
+        assert_ne!(channel.as_slices().1, &[][..]);
+
+        let search = |t| channel.binary_search_by_key(&t, |&(t, _)| t);
+        assert_eq!(search(20), Ok(0));
+        assert_eq!(search(70), Ok(5));
+        assert_eq!(search(90), Ok(7));
+        assert_eq!(search(10), Err(0));
+        assert_eq!(search(55), Err(4));
+        assert_eq!(search(100), Err(8));
+        assert_eq!(channel.iter_window(5, 1).next(), Some(&(70, 7)));
+    }
+
+    #[test]
+    fn range_by_key() {
+        let mut channel = EventChannel::with_capacity(8);
+        // Timestamps 20, 30, 30, 40, .., 80 remain, wrapping around the end
+        channel.iter_write(vec![0, 10, 20, 30, 30, 40, 50, 60, 70, 80]);
+
+        let range = |low, high| {
+            channel
+                .range_by_key(&low, &high, |&t| t)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(range(30, 50), [30, 30, 40, 50]);
+        assert_eq!(range(25, 35), [30, 30]);
+        assert_eq!(range(0, 25), [20]);
+        assert_eq!(range(75, 100), [80]);
+        assert_eq!(range(0, 100).len(), 8);
+        assert!(range(31, 39).is_empty());
+        assert!(range(90, 100).is_empty());
+        assert!(range(50, 30).is_empty());
+    }
+
+    #[test]
+    fn get_stored() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.get_stored(0), None);
+        assert_eq!(channel.get_stored_mut(0), None);
+
+        channel.iter_write(0..3);
+        channel.read(&mut reader);
+        // 2, 3, 4, 5 remain, wrapping around the end
+        channel.iter_write(3..6);
+        assert_eq!(channel.get_stored(0), Some(&2));
+        assert_eq!(channel.get_stored(3), Some(&5));
+        assert_eq!(channel.get_stored(4), None);
+        assert_eq!(channel.get_stored(usize::MAX), None);
+
+        *channel.get_stored_mut(2).unwrap() = 40;
+        assert_eq!(channel.get_stored_mut(4), None);
+        channel.assert_reads(&mut reader, &[3, 40, 5]);
+    }
+
+    #[test]
+    fn min_max_by_key() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert_eq!(channel.min_by_key(|&&(p, _)| p), None);
+        assert_eq!(channel.max_by_key(|&&(p, _)| p), None);
+
+        // (9, 'a') is no longer stored
+        channel.iter_write(vec![(9, 'a'), (2, 'b'), (5, 'c'), (2, 'd'), (5, 'e')]);
+        assert_eq!(channel.min_by_key(|&&(p, _)| p), Some(&(2, 'b')));
+        assert_eq!(channel.max_by_key(|&&(p, _)| p), Some(&(5, 'e')));
+    }
+
+    #[test]
+    fn read_grouped() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+        channel.iter_write((0..5).map(|id| Test { id }));
+
+        let groups = channel.read_grouped(&mut reader, |e| e.id % 2);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&0], [&Test { id: 0 }, &Test { id: 2 }, &Test { id: 4 }]);
+        assert_eq!(groups[&1], [&Test { id: 1 }, &Test { id: 3 }]);
+        assert!(channel.reader_caught_up(&reader));
+    }
+
+    #[test]
+    fn taps() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut channel = EventChannel::with_capacity(2);
+        for tap in 0..2 {
+            let seen = seen.clone();
+            channel.add_tap(move |&e| seen.lock().unwrap().push((tap, e)));
+        }
+
+        channel.iter_write(0..3);
+        channel.single_write(3);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2), (0, 3), (1, 3)]
+        );
+
+        channel.clear_taps();
+        channel.single_write(4);
+        assert_eq!(seen.lock().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn read_coalesced() {
+        let mut channel = EventChannel::new();
+        let first = channel.register_reader();
+        channel.iter_write(0..2);
+        let second = channel.register_reader();
+        channel.iter_write(2..4);
+
+        let mut readers = [first, second];
+        let events = channel.read_coalesced(&mut readers);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert!(readers.iter().all(|r| channel.reader_caught_up(r)));
+
+        channel.single_write(4);
+        let events = channel.read_coalesced(&mut readers[1..]);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [4]);
+        channel.assert_reads(&mut readers[0], &[4]);
+        assert_eq!(channel.read_coalesced(&mut []).len(), 0);
+    }
+
+    #[test]
+    fn event_handle() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        let first = channel.single_write_handle(10).unwrap();
+        channel.iter_write(11..14);
+        let second = channel.single_write_handle(14).unwrap();
+        assert_eq!((first.sequence(), second.sequence()), (0, 4));
+        assert_eq!(channel.get(first), Some(&10));
+        assert_eq!(channel.get(second), Some(&14));
+
+        // The channel has grown to hold all events for the reader
+        channel.read(&mut reader);
+        channel.iter_write(15..23);
+        assert_eq!(channel.get(first), None);
+        assert_eq!(channel.get(second), None);
+
+        let mut channel = EventChannel::with_policy(2, DropPolicy::DropNewest);
+        let _reader = channel.register_reader();
+        assert!(channel.single_write_handle(0).is_some());
+        assert!(channel.single_write_handle(1).is_some());
+        assert!(channel.single_write_handle(2).is_none());
+    }
+
+    #[test]
+    fn reader_staleness() {
+        let mut channel = EventChannel::new();
+        channel.iter_write(0..3);
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.reader_staleness(&reader), 0);
+
+        channel.iter_write(0..3);
+        assert_eq!(channel.reader_staleness(&reader), 3);
+        channel.single_write(3);
+        assert_eq!(channel.reader_staleness(&reader), 4);
+
+        channel.ack(&mut reader, 1);
+        assert_eq!(channel.reader_staleness(&reader), 0);
+        channel.single_write(4);
+        let duplicate = channel.duplicate_reader(&reader);
+        assert_eq!(channel.reader_staleness(&duplicate), 1);
+
+        channel.read(&mut reader);
+        assert_eq!(channel.reader_staleness(&reader), 0);
+        channel.read(&mut reader);
+        assert_eq!(channel.reader_staleness(&reader), 0);
+    }
+
+    #[test]
+    fn snapshot_reader() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+
+        let snapshot = channel.snapshot_reader(&reader);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.iter().cloned().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!((&snapshot).into_iter().max(), Some(&2));
+        assert_eq!(snapshot.iter().sum::<i32>(), 3);
+        assert_eq!(channel.reader_lag(&reader), 3);
+
+        channel.assert_reads(&mut reader, &[0, 1, 2]);
+        assert!(channel.snapshot_reader(&reader).is_empty());
+    }
+
+    #[test]
+    fn read_some() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+        assert!(channel.read_some(&mut reader).is_none());
+
+        channel.iter_write(0..2);
+        let events = channel.read_some(&mut reader).expect("events are pending");
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [0, 1]);
+        assert!(channel.read_some(&mut reader).is_none());
+    }
+
+    #[test]
+    fn to_vec_deque() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert!(channel.to_vec_deque().is_empty());
+
+        channel.iter_write(0..6);
+        assert_ne!(channel.as_slices().1, &[][..]);
+        let events = channel.to_vec_deque();
+        assert!(events.iter().eq(channel.iter_all()));
+        assert_eq!(events, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn from_vec() {
+        let mut channel = EventChannel::from(vec![1, 2, 3]);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(channel.next_sequence(), 3);
+
+        let mut reader = channel.register_reader();
+        channel.assert_reads(&mut reader, &[]);
+        channel.single_write(4);
+        channel.assert_reads(&mut reader, &[4]);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [2, 3, 4]);
+
+        let channel = EventChannel::<i32>::from(vec![]);
+        assert_eq!(channel.iter_all().len(), 0);
+    }
+
+    #[test]
+    fn health() {
+        let mut channel = EventChannel::with_capacity(8);
+        assert_eq!(channel.health(), BufferHealth::Idle);
+
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..5);
+        assert_eq!(channel.health(), BufferHealth::Nominal);
+        channel.single_write(5);
+        assert_eq!(channel.health(), BufferHealth::NearFull);
+        channel.iter_write(6..8);
+        assert_eq!(channel.health(), BufferHealth::Overflowing);
+        assert_eq!(channel.utilization(), 1.0);
+
+        // Growing makes room again
+        channel.single_write(8);
+        assert_eq!(channel.health(), BufferHealth::Nominal);
+        assert_eq!(channel.utilization(), 9.0 / 16.0);
+        channel.read(&mut reader);
+        assert_eq!(channel.health(), BufferHealth::Idle);
+        assert_eq!(channel.utilization(), 0.0);
+    }
+
+    #[test]
+    fn reader_caught_up() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+        assert!(channel.reader_caught_up(&reader));
+
+        channel.iter_write(0..2);
+        assert!(!channel.reader_caught_up(&reader));
+        channel.ack(&mut reader, 1);
+        assert!(!channel.reader_caught_up(&reader));
+        channel.ack(&mut reader, 1);
+        assert!(channel.reader_caught_up(&reader));
+    }
+
+    #[test]
+    fn read_with_age() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+        channel.read(&mut reader);
+
+        channel.iter_write(3..6);
+        assert_eq!(
+            channel.read_with_age(&mut reader).collect::<Vec<_>>(),
+            [(&3, 2), (&4, 1), (&5, 0)]
+        );
+        assert_eq!(channel.read_with_age(&mut reader).count(), 0);
+    }
+
+    #[test]
+    fn read_with_range() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        let (events, range) = channel.read_with_range(&mut reader);
+        assert_eq!((events.len(), range), (0, None));
+
+        channel.iter_write(0..3);
+        let (events, range) = channel.read_with_range(&mut reader);
+        assert_eq!((events.len(), range), (3, Some((0, 2))));
+
+        channel.iter_write(3..6);
+        let (events, range) = channel.read_with_range(&mut reader);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [3, 4, 5]);
+        assert_eq!(range, Some((3, 1)));
+    }
+
+    #[test]
+    fn read_seq() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write(0..3);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write(10..13);
+        assert_eq!(
+            channel.read_seq(&mut reader).collect::<Vec<_>>(),
+            [(3, &10), (4, &11), (5, &12)]
         );
 
+        channel.iter_write(13..16);
         assert_eq!(
-            vec![TestEvent { data: 8 }, TestEvent { data: 9 }],
-            channel.read(&mut reader_id).cloned().collect::<Vec<_>>()
+            channel.read_seq(&mut reader).collect::<Vec<_>>(),
+            [(6, &13), (7, &14), (8, &15)]
         );
+        assert_eq!(channel.read_seq(&mut reader).count(), 0);
     }
 
     #[test]
-    fn read_duplicated() {
+    fn bridge_from() {
+        let (tx, rx) = std::sync::mpsc::channel();
         let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
 
-        // This one shouldn't be seen by either channel
-        channel.single_write(TestEvent { data: 0 });
+        assert_eq!(channel.bridge_from(&rx), 0);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(channel.bridge_from(&rx), 2);
+        tx.send(3).unwrap();
+        drop(tx);
+        assert_eq!(channel.bridge_from(&rx), 1);
 
-        let mut reader1 = channel.register_reader();
+        channel.assert_reads(&mut reader, &[1, 2, 3]);
+    }
 
-        // This one should be seen by both
-        channel.single_write(TestEvent { data: 1 });
+    #[test]
+    fn iter_write_returning() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.single_write(0);
+        let range = channel.iter_write_returning(1..4);
+        assert_eq!((range.start(), range.end(), range.len()), (1, 4, 3));
+        assert_eq!(channel.slice(range).cloned().collect::<Vec<_>>(), [1, 2, 3]);
 
-        let mut reader2 = channel.duplicate_reader(&reader1);
+        let empty = channel.iter_write_returning(None);
+        assert!(empty.is_empty());
+        assert_eq!(channel.slice(empty).len(), 0);
+
+        // Only the events still stored are returned
+        channel.iter_write(4..6);
+        assert_eq!(channel.slice(range).cloned().collect::<Vec<_>>(), [2, 3]);
+        channel.iter_write(6..10);
+        assert_eq!(channel.slice(range).len(), 0);
+
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        let _reader = channel.register_reader();
+        let range = channel.iter_write_returning(0..6);
+        assert_eq!(range.len(), 4);
+        assert_eq!(channel.slice(range).cloned().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn forward_to() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+
+        assert_eq!(channel.forward_to(&mut reader, &tx), 0);
+        channel.iter_write(0..3);
+        assert_eq!(channel.forward_to(&mut reader, &tx), 3);
+        assert_eq!(channel.forward_to(&mut reader, &tx), 0);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [0, 1, 2]);
+
+        channel.single_write(3);
+        drop(rx);
+        assert_eq!(channel.forward_to(&mut reader, &tx), 0);
+        channel.assert_reads(&mut reader, &[3]);
+    }
+
+    #[test]
+    fn write_lossy() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        let mut events: Vec<_> = (0..8).collect();
+        assert_eq!(channel.write_lossy(&mut events), 4);
+        assert!(events.is_empty());
+        channel.assert_reads(&mut reader, &[4, 5, 6, 7]);
+
+        let mut events = vec![8, 9];
+        assert_eq!(channel.write_lossy(&mut events), 0);
+        channel.assert_reads(&mut reader, &[8, 9]);
 
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+        let mut events = vec![10, 11, 12];
+        assert_eq!(channel.write_lossy(&mut events), 2);
+        assert!(events.is_empty());
+        channel.assert_reads(&mut reader, &[0, 1, 2, 10]);
+    }
+
+    #[test]
+    fn adopt_reader() {
+        let mut other = EventChannel::new();
+        let mut channel = EventChannel::new();
+        channel.single_write(0);
+
+        let foreign = other.register_reader_labeled("foreign");
+        other.single_write(1);
+        let mut adopted = channel.adopt_reader(foreign);
+        assert_eq!(adopted.label(), Some("foreign"));
+        channel.single_write(2);
+        channel.assert_reads(&mut adopted, &[2]);
+
+        channel.single_write(3);
+        let mut own = channel.adopt_reader(adopted);
+        channel.assert_reads(&mut own, &[3]);
+
+        other.maintain();
+        assert_eq!(other.slowest_reader_lag(), 0);
+        assert!(!other.would_write());
+    }
+
+    #[test]
+    fn read_contiguous() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write(0..3);
+        let events = channel.read_contiguous(&mut reader);
+        assert!(matches!(events, Cow::Borrowed(_)));
+        assert_eq!(*events, [0, 1, 2]);
+
+        channel.iter_write(3..6);
+        let events = channel.read_contiguous(&mut reader);
+        assert!(matches!(events, Cow::Owned(_)));
+        assert_eq!(*events, [3, 4, 5]);
+
+        assert!(channel.read_contiguous(&mut reader).is_empty());
+    }
+
+    #[test]
+    fn total_lost() {
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        let mut stalled = channel.register_reader();
+        assert_eq!(channel.total_lost(&stalled), 0);
+
+        channel.iter_write(0..6);
+        assert_eq!(channel.total_lost(&stalled), 2);
+        let late = channel.register_reader();
+        channel.single_write(6);
+        channel.iter_write(7..10);
+        assert_eq!(channel.total_lost(&stalled), 6);
+        assert_eq!(channel.total_lost(&late), 4);
+        assert_eq!(channel.stats().events_dropped, 6);
+
+        channel.assert_reads(&mut stalled, &[0, 1, 2, 3]);
+        assert_eq!(channel.total_lost(&stalled), 6);
+    }
+
+    #[test]
+    fn drop_policy() {
+        let mut channel = EventChannel::with_policy(4, DropPolicy::Grow);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..6);
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3, 4, 5]);
+
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+        channel.iter_write(3..6);
+        channel.single_write(6);
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3]);
+        assert_eq!(channel.storage.capacity(), 4);
+
+        channel.iter_write(6..10);
+        channel.assert_reads(&mut reader, &[6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn single_write_with_eviction() {
+        use std::sync::{Arc, Mutex};
+
+        let evicted = Arc::new(Mutex::new(vec![]));
+        let on_evict = |event| {
+            let evicted = evicted.clone();
+            move || evicted.lock().unwrap().push(event)
+        };
+
+        let mut channel = EventChannel::with_capacity(4);
+        for event in 0..3 {
+            channel.single_write_with_eviction(event, on_evict(event));
+        }
+        channel.iter_write(3..5);
+        assert_eq!(*evicted.lock().unwrap(), [0]);
+
+        channel.single_write_with_eviction(5, on_evict(5));
+        channel.iter_write(6..9);
+        assert_eq!(*evicted.lock().unwrap(), [0, 1, 2]);
+        channel.single_write(9);
+        assert_eq!(*evicted.lock().unwrap(), [0, 1, 2, 5]);
+
+        channel.set_paused(true);
+        channel.single_write_with_eviction(10, on_evict(10));
+        assert_eq!(*evicted.lock().unwrap(), [0, 1, 2, 5, 10]);
+    }
+
+    #[test]
+    fn ingest_filter() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.set_ingest_filter(|id: u32| match id % 2 {
+            0 => Some(id * 10),
+            _ => None,
+        });
+
+        channel.single_write(1);
+        channel.single_write(2);
+        channel.iter_write(3..9);
+        channel.iter_write(vec![9, 11]);
+        assert_eq!(channel.stats().writes, 2);
+        channel.assert_reads(&mut reader, &[20, 40, 60, 80]);
+
+        // Events queued while paused pass through the filter only once
+        channel.set_paused(true);
+        channel.iter_write(vec![10, 11]);
+        channel.set_paused(false);
+        channel.assert_reads(&mut reader, &[100]);
+    }
+
+    #[test]
+    fn try_single_write() {
+        let mut channel = EventChannel::with_capacity(2);
+        assert_eq!(channel.try_single_write(0), Ok(()));
+        assert_eq!(channel.try_single_write(1), Ok(()));
+        assert_eq!(channel.try_single_write(2), Ok(()));
+
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.try_single_write(3), Ok(()));
+        assert_eq!(channel.try_single_write(4), Ok(()));
+        assert_eq!(channel.try_single_write(5), Err(5));
+        assert_eq!(channel.storage.capacity(), 2);
+
+        channel.ack(&mut reader, 1);
+        assert_eq!(channel.try_single_write(5), Ok(()));
+        channel.assert_reads(&mut reader, &[4, 5]);
+    }
+
+    #[test]
+    fn write_coalesced() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert!(!channel.write_coalesced((0, 0), |e| e.0));
+        assert!(!channel.write_coalesced((0, 1), |e| e.0));
+
+        let mut fast = channel.register_reader();
+        let mut slow = channel.register_reader();
+        assert!(!channel.write_coalesced((0, 2), |e| e.0));
+        assert!(!channel.write_coalesced((1, 3), |e| e.0));
+        assert!(channel.write_coalesced((0, 4), |e| e.0));
+        assert!(channel.write_coalesced((1, 5), |e| e.0));
+        channel.assert_reads(&mut fast, &[(0, 4), (1, 5)]);
+
+        // `slow` hasn't read them yet, but `fast` has
+        assert!(!channel.write_coalesced((0, 6), |e| e.0));
+        channel.assert_reads(&mut slow, &[(0, 4), (1, 5), (0, 6)]);
+
+        channel.set_paused(true);
+        assert!(!channel.write_coalesced((1, 7), |e| e.0));
+        assert!(channel.write_coalesced((1, 8), |e| e.0));
+        channel.set_paused(false);
+        channel.assert_reads(&mut fast, &[(0, 6), (1, 8)]);
+        assert_eq!(channel.next_sequence(), 6);
+    }
+
+    #[test]
+    fn paused() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.single_write(0);
+
+        channel.set_paused(true);
+        channel.set_paused(true);
+        assert!(channel.is_paused());
+        channel.single_write(1);
+        channel.iter_write(2..6);
+        channel.iter_write(None);
+        assert_eq!(channel.single_write_handle(6), None);
+        assert_eq!(channel.next_sequence(), 1);
+        channel.assert_reads(&mut reader, &[0]);
+
+        channel.set_paused(false);
+        assert!(!channel.is_paused());
+        channel.single_write(7);
+        assert_eq!(channel.next_sequence(), 8);
+        channel.assert_reads(&mut reader, &[1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn atomic_reader() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.single_write(0);
+        let reader = channel.register_atomic_reader();
+        let mut other = channel.register_reader();
+
+        channel.iter_write(1..4);
+        assert_eq!(channel.atomic_reader_lag(&reader), 3);
+        channel.assert_reads(&mut other, &[1, 2, 3]);
+        // The atomic reader keeps its events stored
+        channel.iter_write(4..7);
+        assert_eq!(channel.read_atomic(&reader).cloned().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(channel.read_atomic(&reader).len(), 0);
+        assert_eq!(channel.storage.capacity(), 8);
+
+        channel.assert_reads(&mut other, &[4, 5, 6]);
+
+        channel.iter_write(7..15);
+        channel.assert_reads(&mut other, &[7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(channel.storage.capacity(), 8);
+        // A dropped reader no longer keeps events stored
+        drop(reader);
+        channel.iter_write(15..23);
+        assert_eq!(channel.storage.capacity(), 8);
+
+        let reader = channel.register_atomic_reader();
+        channel.iter_write(23..28);
+        let batch = |max| channel.read_atomic_batch(&reader, max).cloned().collect::<Vec<_>>();
+        assert_eq!(batch(2), [23, 24]);
+        assert_eq!(channel.atomic_reader_lag(&reader), 3);
+        assert_eq!(batch(4), [25, 26, 27]);
+        assert_eq!(batch(4), []);
+    }
+
+    #[test]
+    fn debug_dump() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write(0..3);
+        let mut reader = channel.register_reader();
+        let dropped = channel.register_reader();
+        let stuck = channel.register_reader();
+        drop(dropped);
+        channel.iter_write(3..7);
+        channel.ack(&mut reader, 3);
+
+        let dump = channel.debug_dump();
+        assert_eq!((dump.next_sequence, dump.oldest, dump.capacity), (7, 3, 4));
+        let expected = [
+            ReaderDump {
+                id: reader.id(),
+                position: 6,
+                lag: 1,
+            },
+            ReaderDump {
+                id: stuck.id(),
+                position: 3,
+                lag: 4,
+            },
+        ];
+        assert_eq!(dump.readers, expected);
+        assert_eq!(dump.events.clone().collect::<Vec<_>>(), [&3, &4, &5, &6]);
         assert_eq!(
-            channel.read(&mut reader1).cloned().collect::<Vec<_>>(),
-            vec![TestEvent { data: 1 }]
+            dump.to_string(),
+            "7 events written, 4 stored, capacity 4\n\
+             reader 0: at 6, lag 1\n\
+             reader 2: at 3, lag 4\n\
+             3: 3\n4: 4\n5: 5\n6: 6\n"
         );
+    }
 
-        // This one should also be seen by both
-        channel.single_write(TestEvent { data: 2 });
+    #[test]
+    fn accessors() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert!(channel.is_empty());
+        assert_eq!(channel.latest(), None);
+
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.oldest_unread(&reader), None);
+        channel.iter_write(0..3);
+        assert_eq!((channel.len(), channel.capacity()), (3, 4));
+        assert_eq!(channel.latest(), Some(&2));
+        assert_eq!(channel.oldest_unread(&reader), Some(&0));
+
+        channel.ack(&mut reader, 2);
+        channel.iter_write(3..6);
+        assert_eq!((channel.len(), channel.capacity()), (4, 4));
+        assert_eq!(channel.oldest_unread(&reader), Some(&2));
+        assert_eq!((&channel).into_iter().collect::<Vec<_>>(), [&2, &3, &4, &5]);
+    }
+
+    #[test]
+    fn at_lag() {
+        let mut channel = EventChannel::with_capacity(4);
+        assert_eq!(channel.at_lag(0), None);
+
+        channel.iter_write(0..2);
+        assert_eq!(channel.at_lag(0), Some(&1));
+        assert_eq!(channel.at_lag(1), Some(&0));
+        assert_eq!(channel.at_lag(2), None);
+
+        channel.iter_write(2..7);
+        assert_eq!(channel.at_lag(0), Some(&6));
+        assert_eq!(channel.at_lag(3), Some(&3));
+        assert_eq!(channel.at_lag(4), None);
+        assert_eq!(channel.at_lag(usize::MAX), None);
+    }
+
+    #[test]
+    fn checkpoint_restore() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut other = channel.register_reader();
+        channel.iter_write(0..2);
+        let start = channel.checkpoint(&reader);
+        channel.iter_write(2..4);
+        assert_eq!(start.sequence(), 0);
+
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3]);
+        let end = channel.checkpoint(&reader);
+        assert_eq!(channel.restore(&mut other, start), Err(CheckpointExpired));
+        assert_eq!(channel.restore(&mut reader, start), Ok(4));
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3]);
+        assert_eq!(channel.restore(&mut reader, start), Ok(4));
+        assert_eq!(channel.restore(&mut reader, end), Ok(0));
+        channel.assert_reads(&mut reader, &[]);
+
+        // The restored reader keeps its events from being overwritten
+        channel.restore(&mut reader, start).unwrap();
+        channel.read(&mut other);
+        channel.iter_write(4..6);
+        assert_eq!(channel.storage.capacity(), 8);
+        channel.assert_reads(&mut reader, &[0, 1, 2, 3, 4, 5]);
+
+        channel.iter_write(6..12);
+        channel.read(&mut other);
+        assert_eq!(channel.restore(&mut reader, start), Err(CheckpointExpired));
+        channel.assert_reads(&mut reader, &[6, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn read_uncommitted() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut other = channel.register_reader();
+        channel.iter_write(0..3);
+
+        let (events, token) = channel.read_uncommitted(&reader);
+        assert_eq!(events.cloned().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(token.len(), 3);
+        // Events written in the meantime are kept
+        channel.iter_write(3..8);
+        assert_eq!(channel.commit(&mut other, token), Err(StaleCommit));
+        assert_eq!(channel.commit(&mut reader, token), Ok(3));
+        assert_eq!(channel.commit(&mut reader, token), Err(StaleCommit));
+        channel.assert_reads(&mut reader, &[3, 4, 5, 6, 7]);
+
+        let (_, token) = channel.read_uncommitted(&other);
+        channel.ack(&mut other, 1);
+        assert_eq!(channel.commit(&mut other, token), Err(StaleCommit));
+        channel.assert_reads(&mut other, &[1, 2, 3, 4, 5, 6, 7]);
+
+        let (events, token) = channel.read_uncommitted(&reader);
+        assert!(token.is_empty() && events.is_empty());
+        assert_eq!(channel.commit(&mut reader, token), Ok(0));
+    }
+
+    #[test]
+    fn read_try_for_each() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..5);
+
+        let mut seen = vec![];
+        let result = channel.read_try_for_each(&mut reader, |&e| {
+            if e == 2 {
+                return Err(e);
+            }
+            seen.push(e);
+
+            Ok(())
+        });
+        assert_eq!(result, Err(2));
+        assert_eq!(seen, [0, 1]);
+
+        let result = channel.read_try_for_each(&mut reader, |&e| {
+            seen.push(e);
+
+            Ok::<_, ()>(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+        assert_eq!(channel.reader_lag(&reader), 0);
+    }
+
+    #[test]
+    fn clone() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.iter_write(0..3);
+
+        let mut clone = channel.clone();
+        assert!(clone.iter_all().eq(channel.iter_all()));
+        assert_eq!(clone.next_sequence(), 3);
+        let mut clone_reader = clone.register_reader();
+        clone.single_write(3);
+        clone.assert_reads(&mut clone_reader, &[3]);
+        channel.assert_reads(&mut reader, &[0, 1, 2]);
+
+        clone.single_write(4);
+        clone.clone_from(&channel);
+        assert!(clone.iter_all().eq(channel.iter_all()));
+        clone.assert_reads(&mut clone_reader, &[]);
+    }
+
+    #[test]
+    fn swap_data() {
+        let mut front = EventChannel::with_capacity(4);
+        let mut back = EventChannel::with_capacity(8);
+        let mut front_reader = front.register_reader();
+        let mut back_reader = back.register_reader();
+        front.iter_write(0..3);
+        back.iter_write(10..16);
+
+        front.swap_data(&mut back);
+        assert_eq!(front.iter_all().cloned().collect::<Vec<_>>(), [10, 11, 12, 13, 14, 15]);
+        assert_eq!(back.iter_all().cloned().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!((front.next_sequence(), back.next_sequence()), (6, 3));
+        front.assert_reads(&mut front_reader, &[]);
+        back.assert_reads(&mut back_reader, &[]);
+
+        front.iter_write(16..20);
+        back.single_write(3);
+        front.assert_reads(&mut front_reader, &[16, 17, 18, 19]);
+        back.assert_reads(&mut back_reader, &[3]);
+        assert_eq!(front.iter_all().len(), 8);
+    }
+
+    #[test]
+    fn compact() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write(0..3);
+        let mut reader = channel.register_reader();
+        channel.iter_write(3..5);
+        let mut late = channel.register_reader();
+        channel.iter_write(5..6);
+
+        assert_eq!(channel.as_slices(), (&[2, 3][..], &[4, 5][..]));
+        channel.compact();
+        assert_eq!(channel.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+
+        channel.assert_reads(&mut reader, &[3, 4, 5]);
+        channel.assert_reads(&mut late, &[5]);
+        channel.iter_write(6..8);
+        channel.assert_reads(&mut reader, &[6, 7]);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn shrink_to() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut behind = channel.register_reader();
+        channel.iter_write(0..12);
+        channel.read(&mut reader);
+        channel.ack(&mut behind, 7);
+        channel.iter_write(12..14);
+        assert_eq!(channel.storage.capacity(), 16);
+
+        // `behind` still has 7..14 pending
+        channel.shrink_to(4);
+        assert_eq!(channel.storage.capacity(), 7);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [7, 8, 9, 10, 11, 12, 13]);
+        channel.assert_reads(&mut behind, &[7, 8, 9, 10, 11, 12, 13]);
+
+        channel.shrink_to(0);
+        assert_eq!(channel.storage.capacity(), 2);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [12, 13]);
+        channel.iter_write(14..16);
+        channel.assert_reads(&mut reader, &[12, 13, 14, 15]);
+        channel.assert_reads(&mut behind, &[14, 15]);
+        let mut resumed = channel.reader_at_sequence(13);
+        channel.assert_reads(&mut resumed, &[13, 14, 15]);
+
+        channel.shrink_to(8);
+        assert_eq!(channel.storage.capacity(), 4);
+    }
+
+    #[test]
+    fn expire_before() {
+        let mut channel = EventChannel::with_capacity(8);
+        let mut reader = channel.register_reader();
+        let mut ahead = channel.register_reader();
+        channel.iter_write(0..3);
+        let tick = channel.next_sequence();
+        channel.iter_write(3..6);
+        channel.ack(&mut ahead, 4);
+
+        assert_eq!(channel.expire_before(tick), 3);
+        assert_eq!(channel.expire_before(tick), 0);
+        assert_eq!(channel.iter_all().cloned().collect::<Vec<_>>(), [3, 4, 5]);
+        channel.assert_reads(&mut reader, &[3, 4, 5]);
+        channel.assert_reads(&mut ahead, &[4, 5]);
+
+        channel.iter_write(6..8);
+        assert_eq!(channel.expire_before(u64::MAX), 5);
+        assert_eq!(channel.reader_lag(&reader), 0);
+        channel.iter_write(8..16);
+        assert_eq!(channel.storage.capacity(), 8);
+        channel.assert_reads(&mut reader, &(8..16).collect::<Vec<_>>());
+        assert_eq!(channel.stats().events_expired, 8);
+        assert_eq!(channel.stats().loss_incidents, 0);
+    }
+
+    #[test]
+    fn clear_consumed() {
+        use std::rc::Rc;
+
+        let event = Rc::new(());
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut cursor = channel.register_cursor();
+        channel.iter_write(vec![event.clone(); 3]);
+        channel.ack(&mut reader, 2);
+
+        assert_eq!(channel.clear_consumed(), 2);
+        assert_eq!(Rc::strong_count(&event), 2);
+        assert_eq!(channel.iter_all().len(), 1);
+        assert_eq!(channel.lost_preview(&cursor), 2);
+        assert_eq!(channel.read_cursor(&mut cursor).len(), 1);
+
+        // Writing fills the cleared slots first, wrapping around
+        channel.iter_write(vec![event.clone(); 3]);
+        assert_eq!(channel.storage.capacity(), 4);
+        assert_eq!(channel.read(&mut reader).len(), 4);
+        assert_eq!(channel.clear_consumed(), 4);
+        assert_eq!(Rc::strong_count(&event), 1);
+        assert_eq!(channel.clear_consumed(), 0);
+
+        channel.iter_write(vec![event.clone(); 6]);
+        assert_eq!(channel.read(&mut reader).len(), 6);
+        drop(channel);
+        assert_eq!(Rc::strong_count(&event), 1);
+    }
+
+    #[test]
+    fn resize() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let mut behind = channel.register_reader();
+        channel.iter_write(0..3);
+        channel.read(&mut reader);
+        channel.iter_write(3..5);
+
+        // Not a multiple of the old capacity, and with wrapped events
+        channel.resize(7);
+        assert_eq!(channel.storage.capacity(), 7);
+        channel.iter_write(5..7);
+        assert_eq!(channel.storage.capacity(), 7);
+        channel.assert_reads(&mut reader, &[3, 4, 5, 6]);
+        channel.assert_reads(&mut behind, &[0, 1, 2, 3, 4, 5, 6]);
+
+        channel.iter_write(7..10);
+        channel.resize(3);
+        assert_eq!(channel.storage.capacity(), 3);
+        channel.resize(16);
+        channel.assert_reads(&mut reader, &[7, 8, 9]);
+        channel.iter_write(10..14);
+        channel.shrink_to_fit();
+        assert_eq!(channel.storage.capacity(), 7);
+        channel.assert_reads(&mut behind, &[7, 8, 9, 10, 11, 12, 13]);
+        channel.shrink_to_fit();
+        assert_eq!(channel.storage.capacity(), 4);
+        channel.assert_reads(&mut reader, &[10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn copy_retained_into() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write(0..3);
+        assert_eq!(channel.as_ptr(), channel.as_slices().0.as_ptr());
+
+        // 2, 3, 4, 5 remain, wrapping around the end
+        channel.iter_write(3..6);
+        assert_eq!(channel.as_slices(), (&[2, 3][..], &[4, 5][..]));
+
+        let len = channel.iter_all().len();
+        let mut dst = Vec::with_capacity(len);
+        unsafe {
+            channel.copy_retained_into(dst.as_mut_ptr());
+            dst.set_len(len);
+        }
+        assert_eq!(dst, channel.iter_all().cloned().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write((0..6).map(|i| i.to_string()));
+
+        let expected: Vec<_> = channel.iter_all().cloned().collect();
+        assert_eq!(expected, ["2", "3", "4", "5"]);
+        assert_eq!(channel.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn recycle_sink() {
+        use std::sync::{Arc, Mutex};
+
+        let recycled = Arc::new(Mutex::new(Vec::new()));
+        let mut channel = EventChannel::with_capacity(2);
+        channel.set_recycle_sink({
+            let recycled = recycled.clone();
+            move |e: Vec<u8>| recycled.lock().unwrap().push(e)
+        });
+
+        channel.single_write(vec![1]);
+        channel.single_write(vec![2]);
+        assert!(recycled.lock().unwrap().is_empty());
+
+        channel.single_write(vec![3]);
+        channel.single_write(vec![4]);
+        assert_eq!(*recycled.lock().unwrap(), vec![vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn reader_max_age() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader_max_age(3);
+        let mut acked = channel.register_reader_max_age(3);
+        let mut duplicate = channel.duplicate_reader(&reader);
+
+        channel.iter_write(0..2);
+        assert_eq!(channel.reader_lag(&reader), 2);
+        channel.assert_reads(&mut reader, &[0, 1]);
+
+        channel.iter_write(2..8);
+        assert_eq!(channel.reader_lag(&reader), 3);
+        assert_eq!(channel.peek(&acked).cloned().collect::<Vec<_>>(), [5, 6, 7]);
+        channel.assert_reads(&mut reader, &[5, 6, 7]);
+        channel.assert_reads(&mut duplicate, &[5, 6, 7]);
+        assert_eq!(channel.ack(&mut acked, 1), 1);
+        channel.assert_reads(&mut acked, &[6, 7]);
+
+        channel.single_write(8);
+        channel.assert_reads(&mut reader, &[8]);
+    }
+
+    #[test]
+    fn reader_at_sequence() {
+        let mut channel = EventChannel::with_capacity(4);
+        channel.iter_write(0..6);
+
+        let mut resumed = channel.reader_at_sequence(3);
+        let mut evicted = channel.reader_at_sequence(0);
+        let mut full = channel.reader_at_sequence(2);
+        let mut future = channel.reader_at_sequence(100);
+        channel.single_write(6);
+
+        channel.assert_reads(&mut resumed, &[3, 4, 5, 6]);
+        channel.assert_reads(&mut evicted, &[2, 3, 4, 5, 6]);
+        channel.assert_reads(&mut full, &[2, 3, 4, 5, 6]);
+        channel.assert_reads(&mut future, &[6]);
+    }
+
+    #[test]
+    fn labeled_reader() {
+        let mut channel = EventChannel::<i32>::new();
+        let physics = channel.register_reader_labeled("physics");
+        let duplicate = channel.duplicate_reader(&physics);
+        let plain = channel.register_reader();
+
+        assert_eq!(physics.label(), Some("physics"));
+        assert_eq!(duplicate.label(), Some("physics"));
+        assert_eq!(plain.label(), None);
+        assert!(format!("{:?}", physics).contains("\"physics\""));
+    }
+
+    #[test]
+    fn write_slice_twice() {
+        let mut channel = EventChannel::new();
+        let mut reader = channel.register_reader();
+
+        let events = [1, 2, 3];
+        channel.iter_write(events.iter().cloned());
+        channel.iter_write(events.iter().cloned());
+        channel.assert_reads(&mut reader, &[1, 2, 3, 1, 2, 3]);
+    }
 
+    #[test]
+    fn read_filter() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write(0..7);
         assert_eq!(
-            channel.read(&mut reader1).cloned().collect::<Vec<_>>(),
-            vec![TestEvent { data: 2 }]
+            channel
+                .read_filter(&mut reader, |e| e % 2 == 0)
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![0, 2, 4, 6]
         );
+        assert_eq!(channel.reader_lag(&reader), 0);
+        assert_eq!(channel.read_filter(&mut reader, |_| true).count(), 0);
+    }
+
+    #[test]
+    fn stats() {
+        let mut channel = EventChannel::with_capacity(4);
+
+        let mut reader = channel.register_reader();
+        let mut cursor = channel.register_cursor();
+
+        channel.iter_write(0..3);
+        channel.iter_write(Vec::new());
+        channel.read(&mut reader).for_each(drop);
+        channel.iter_write(3..10);
+        channel.read(&mut reader).for_each(drop);
+        channel.read_cursor(&mut cursor).for_each(drop);
+
+        let stats = channel.stats();
         assert_eq!(
-            channel.read(&mut reader2).cloned().collect::<Vec<_>>(),
-            vec![TestEvent { data: 1 }, TestEvent { data: 2 }]
+            stats,
+            Stats {
+                writes: 2,
+                reads: 3,
+                events_written: 10,
+                events_read: 3 + 7 + 8,
+                events_lost: 2,
+                events_dropped: 0,
+                events_expired: 0,
+                loss_incidents: 1,
+            }
         );
+
+        channel.reset_stats();
+        assert_eq!(channel.stats(), Stats::default());
+    }
+
+    #[test]
+    fn lag_alarm() {
+        use std::sync::{Arc, Mutex};
+
+        let alarms = Arc::new(Mutex::new(vec![]));
+        let mut channel = EventChannel::with_capacity(4);
+        channel.set_lag_alarm(3, {
+            let alarms = alarms.clone();
+            move |id, lag| alarms.lock().unwrap().push((id, lag))
+        });
+
+        let mut reader = channel.register_reader();
+        let stuck = channel.register_reader();
+        let dropped = channel.register_reader();
+        drop(dropped);
+        channel.iter_write(0..3);
+        assert_eq!(*alarms.lock().unwrap(), []);
+        channel.single_write(3);
+        assert_eq!(*alarms.lock().unwrap(), [(reader.id(), 4), (stuck.id(), 4)]);
+
+        // Only crossing the threshold triggers the alarm
+        channel.read(&mut reader);
+        channel.iter_write(4..7);
+        channel.iter_write(7..12);
+        assert_eq!(alarms.lock().unwrap().len(), 3);
+        assert_eq!(alarms.lock().unwrap()[2], (reader.id(), 8));
+    }
+
+    #[test]
+    fn loss_hook() {
+        use std::sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        };
+
+        let lost = Arc::new(AtomicU64::new(0));
+        let mut channel = EventChannel::with_policy(4, DropPolicy::DropNewest);
+        channel.set_loss_hook({
+            let lost = lost.clone();
+            move |n| {
+                lost.fetch_add(n, Ordering::SeqCst);
+            }
+        });
+
+        let mut cursor = channel.register_cursor();
+        channel.iter_write(0..6);
+        channel.read_cursor(&mut cursor).for_each(drop);
+        assert_eq!(lost.load(Ordering::SeqCst), 2);
+
+        let _reader = channel.register_reader();
+        channel.iter_write(6..11);
+        channel.read_cursor(&mut cursor).for_each(drop);
+        assert_eq!(lost.load(Ordering::SeqCst), 2 + 1);
+
+        let stats = channel.stats();
+        assert_eq!((stats.events_lost, stats.events_dropped, stats.loss_incidents), (2, 1, 2));
     }
 
     #[derive(Clone, Debug, PartialEq, Eq)]
@@ -0,0 +1,202 @@
+//! Channels split into independent streams by key.
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Event, EventChannel, EventIterator, ReaderId};
+
+/// A channel with a separate stream of events for every key, e.g. an entity
+/// or a topic.
+///
+/// Internally, every key has an `EventChannel` of its own, created when the
+/// first event with that key is written. A single `PartitionedReader` reads
+/// from all of them, one key at a time with `read_partition`, or all at once
+/// with `read_all`.
+///
+/// A reader receives the events written after it was registered. For keys
+/// which are new to the reader, it starts at the oldest event still stored,
+/// since all of them were written later. Up to then, the channel of such a
+/// key may overwrite its events once it is full, like any channel without
+/// readers.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::PartitionedChannel;
+///
+/// let mut channel = PartitionedChannel::new();
+/// let mut reader = channel.register_reader();
+///
+/// channel.single_write("player", "jump");
+/// channel.single_write("enemy", "attack");
+/// channel.single_write("player", "land");
+///
+/// let player: Vec<_> = channel.read_partition(&mut reader, &"player").collect();
+/// assert_eq!(player, vec![&"jump", &"land"]);
+/// assert_eq!(channel.read_all(&mut reader).collect::<Vec<_>>(), vec![(&"enemy", &"attack")]);
+/// ```
+#[derive(Debug)]
+pub struct PartitionedChannel<K, E> {
+    partitions: HashMap<K, EventChannel<E>>,
+    size: usize,
+}
+
+/// A reader of a `PartitionedChannel`, obtained from
+/// `PartitionedChannel::register_reader`.
+#[derive(Debug)]
+pub struct PartitionedReader<K, E: 'static> {
+    readers: HashMap<K, ReaderId<E>>,
+}
+
+impl<K, E> PartitionedChannel<K, E>
+where
+    K: Clone + Eq + Hash,
+    E: Event,
+{
+    /// Create a new `PartitionedChannel` with a default size of 64 per key.
+    pub fn new() -> Self {
+        Self::with_capacity(64)
+    }
+
+    /// Create a new `PartitionedChannel` with the given starting capacity
+    /// per key.
+    pub fn with_capacity(size: usize) -> Self {
+        PartitionedChannel {
+            partitions: HashMap::new(),
+            size,
+        }
+    }
+
+    /// Write a single event with the given key.
+    pub fn single_write(&mut self, key: K, event: E) {
+        self.partition_mut(key).single_write(event);
+    }
+
+    /// Write an iterator of events, all with the given key.
+    pub fn iter_write<I>(&mut self, key: K, iter: I)
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.partition_mut(key).iter_write(iter);
+    }
+
+    /// Returns the channel of `key`, creating it if there is none yet.
+    pub fn partition_mut(&mut self, key: K) -> &mut EventChannel<E> {
+        let size = self.size;
+
+        self.partitions
+            .entry(key)
+            .or_insert_with(|| EventChannel::with_capacity(size))
+    }
+
+    /// Returns the channel of `key`, if there is one.
+    pub fn partition(&self, key: &K) -> Option<&EventChannel<E>> {
+        self.partitions.get(key)
+    }
+
+    /// Iterates over the keys which have a channel.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.partitions.keys()
+    }
+
+    /// Register a new reader, which receives the events written afterwards
+    /// for every key.
+    pub fn register_reader(&mut self) -> PartitionedReader<K, E> {
+        let readers = self
+            .partitions
+            .iter_mut()
+            .map(|(key, channel)| (key.clone(), channel.register_reader()))
+            .collect();
+
+        PartitionedReader { readers }
+    }
+
+    /// Read the events with the given key written since the last read of
+    /// that key with `reader`.
+    ///
+    /// If there is no channel for `key` yet, it is created, so `reader`
+    /// doesn't miss any of its events.
+    pub fn read_partition(
+        &mut self,
+        reader: &mut PartitionedReader<K, E>,
+        key: &K,
+    ) -> EventIterator<'_, E> {
+        let channel = self.partition_mut(key.clone());
+        let reader_id = reader
+            .readers
+            .entry(key.clone())
+            .or_insert_with(|| channel.reader_at_sequence(0));
+
+        channel.read(reader_id)
+    }
+
+    /// Read the events of every key written since the last read with
+    /// `reader`, along with their key.
+    ///
+    /// The events of one key are read in the order they were written, but
+    /// the keys are in no particular order.
+    pub fn read_all<'a>(
+        &'a mut self,
+        reader: &'a mut PartitionedReader<K, E>,
+    ) -> impl Iterator<Item = (&'a K, &'a E)> + 'a {
+        for (key, channel) in &mut self.partitions {
+            if !reader.readers.contains_key(key) {
+                let reader_id = channel.reader_at_sequence(0);
+                reader.readers.insert(key.clone(), reader_id);
+            }
+        }
+
+        let readers = &mut reader.readers;
+        self.partitions.iter().flat_map(move |(key, channel)| {
+            let reader_id = readers.get_mut(key).expect("Bug in shrev");
+
+            channel.read(reader_id).map(move |event| (key, event))
+        })
+    }
+
+    /// Returns how many events with the given key `reader` has not read yet.
+    pub fn reader_lag(&self, reader: &PartitionedReader<K, E>, key: &K) -> usize {
+        match (self.partitions.get(key), reader.readers.get(key)) {
+            (Some(channel), Some(reader_id)) => channel.reader_lag(reader_id),
+            (Some(channel), None) => channel.iter_all().len(),
+            (None, _) => 0,
+        }
+    }
+}
+
+impl<K, E> Default for PartitionedChannel<K, E>
+where
+    K: Clone + Eq + Hash,
+    E: Event,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partitions() {
+        let mut channel = PartitionedChannel::with_capacity(4);
+        channel.iter_write(0, 0..3);
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.read_partition(&mut reader, &1).len(), 0);
+
+        channel.iter_write(1, 10..13);
+        channel.single_write(0, 3);
+        assert_eq!(channel.reader_lag(&reader, &0), 1);
+        assert_eq!(channel.reader_lag(&reader, &1), 3);
+
+        let read: Vec<_> = channel.read_partition(&mut reader, &1).cloned().collect();
+        assert_eq!(read, [10, 11, 12]);
+
+        channel.iter_write(2, 20..22);
+        let mut read: Vec<_> = channel.read_all(&mut reader).map(|(k, e)| (*k, *e)).collect();
+        read.sort();
+        assert_eq!(read, [(0, 3), (2, 20), (2, 21)]);
+        assert_eq!(channel.read_all(&mut reader).count(), 0);
+    }
+}
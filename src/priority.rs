@@ -0,0 +1,160 @@
+//! Channels with several priorities of events.
+
+use std::iter::Chain;
+
+use crate::{Event, EventChannel, EventIterator, ReaderId};
+
+/// The priority of an event written to a `PriorityChannel`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// Read before all events of normal priority.
+    High,
+    /// The priority of events written with `PriorityChannel::single_write`.
+    #[default]
+    Normal,
+}
+
+/// A channel where readers receive the events of high priority before the
+/// ones of normal priority.
+///
+/// Events of the same priority are read in the order they were written.
+/// Internally, every priority has a channel of its own, and reads read from
+/// all of them.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::{Priority, PriorityChannel};
+///
+/// let mut channel = PriorityChannel::new();
+/// let mut reader = channel.register_reader();
+///
+/// channel.single_write("move");
+/// channel.write_with_priority("quit", Priority::High);
+/// channel.single_write("jump");
+///
+/// assert_eq!(channel.read(&mut reader).collect::<Vec<_>>(), vec![&"quit", &"move", &"jump"]);
+/// ```
+#[derive(Debug)]
+pub struct PriorityChannel<E> {
+    high: EventChannel<E>,
+    normal: EventChannel<E>,
+}
+
+/// A reader of a `PriorityChannel`, obtained from
+/// `PriorityChannel::register_reader`.
+#[derive(Debug)]
+pub struct PriorityReader<E: 'static> {
+    high: ReaderId<E>,
+    normal: ReaderId<E>,
+}
+
+impl<E> PriorityChannel<E>
+where
+    E: Event,
+{
+    /// Create a new `PriorityChannel` with a default size of 64 per
+    /// priority.
+    pub fn new() -> Self {
+        Self::with_capacity(64)
+    }
+
+    /// Create a new `PriorityChannel` with the given starting capacity per
+    /// priority.
+    pub fn with_capacity(size: usize) -> Self {
+        PriorityChannel {
+            high: EventChannel::with_capacity(size),
+            normal: EventChannel::with_capacity(size),
+        }
+    }
+
+    /// Write a single event of normal priority.
+    pub fn single_write(&mut self, event: E) {
+        self.write_with_priority(event, Priority::Normal);
+    }
+
+    /// Write a single event of the given priority.
+    pub fn write_with_priority(&mut self, event: E, priority: Priority) {
+        self.lane_mut(priority).single_write(event);
+    }
+
+    /// Write an iterator of events, all of the given priority.
+    pub fn iter_write_with_priority<I>(&mut self, iter: I, priority: Priority)
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.lane_mut(priority).iter_write(iter);
+    }
+
+    fn lane_mut(&mut self, priority: Priority) -> &mut EventChannel<E> {
+        match priority {
+            Priority::High => &mut self.high,
+            Priority::Normal => &mut self.normal,
+        }
+    }
+
+    /// Register a new reader.
+    ///
+    /// See `EventChannel::register_reader`.
+    pub fn register_reader(&mut self) -> PriorityReader<E> {
+        PriorityReader {
+            high: self.high.register_reader(),
+            normal: self.normal.register_reader(),
+        }
+    }
+
+    /// Read the events written since the last read with `reader`, first the
+    /// ones of high priority, then the ones of normal priority.
+    pub fn read(
+        &self,
+        reader: &mut PriorityReader<E>,
+    ) -> Chain<EventIterator<'_, E>, EventIterator<'_, E>> {
+        let high = self.high.read(&mut reader.high);
+
+        high.chain(self.normal.read(&mut reader.normal))
+    }
+
+    /// Returns how many events of the given priority `reader` has not read
+    /// yet.
+    pub fn reader_lag(&self, reader: &PriorityReader<E>, priority: Priority) -> usize {
+        match priority {
+            Priority::High => self.high.reader_lag(&reader.high),
+            Priority::Normal => self.normal.reader_lag(&reader.normal),
+        }
+    }
+}
+
+impl<E> Default for PriorityChannel<E>
+where
+    E: Event,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_order() {
+        let mut channel = PriorityChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        channel.iter_write_with_priority(0..3, Priority::Normal);
+        channel.write_with_priority(10, Priority::High);
+        channel.single_write(3);
+        channel.iter_write_with_priority(11..13, Priority::High);
+        assert_eq!(channel.reader_lag(&reader, Priority::High), 3);
+        assert_eq!(channel.reader_lag(&reader, Priority::Normal), 4);
+
+        let read: Vec<_> = channel.read(&mut reader).cloned().collect();
+        assert_eq!(read, [10, 11, 12, 0, 1, 2, 3]);
+
+        channel.single_write(4);
+        assert_eq!(channel.read(&mut reader).cloned().collect::<Vec<_>>(), [4]);
+        assert_eq!(channel.reader_lag(&reader, Priority::Normal), 0);
+    }
+}
@@ -0,0 +1,125 @@
+//! Recording the events written to a channel.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{Event, EventChannel};
+
+/// Records every event written to a channel along with its sequence number,
+/// so the events can be inspected or replayed later, e.g. to reproduce a
+/// bug.
+///
+/// A recorder is attached to a channel with a tap, see
+/// `EventChannel::add_tap`, so it receives exactly the events which are
+/// stored, in the order they are written. It records until it is dropped or
+/// the taps of the channel are cleared.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::{EventChannel, Recorder};
+///
+/// let mut channel = EventChannel::new();
+/// channel.single_write("before");
+/// let recorder = Recorder::attach(&mut channel);
+/// channel.iter_write(vec!["spawn", "despawn"]);
+///
+/// assert_eq!(recorder.events(), vec![(1, "spawn"), (2, "despawn")]);
+///
+/// let mut replay = EventChannel::new();
+/// let mut reader = replay.register_reader();
+/// recorder.replay_into(&mut replay);
+/// assert_eq!(replay.read(&mut reader).collect::<Vec<_>>(), vec![&"spawn", &"despawn"]);
+/// ```
+#[derive(Debug)]
+pub struct Recorder<T> {
+    log: Arc<Mutex<Vec<(u64, T)>>>,
+}
+
+impl<T> Recorder<T>
+where
+    T: Event + Clone + Send,
+{
+    /// Creates a recorder which records all events written to `channel` from
+    /// now on.
+    pub fn attach(channel: &mut EventChannel<T>) -> Self {
+        let log = Arc::new(Mutex::new(vec![]));
+        let mut sequence = channel.next_sequence();
+        // Weak, so a dropped recorder doesn't keep collecting events.
+        let weak = Arc::downgrade(&log);
+        channel.add_tap(move |event: &T| {
+            if let Some(log) = weak.upgrade() {
+                log.lock()
+                    .expect("`Recorder` lock poisoned")
+                    .push((sequence, event.clone()));
+            }
+            sequence += 1;
+        });
+
+        Recorder { log }
+    }
+
+    /// Returns the recorded events along with their sequence numbers, from
+    /// the oldest to the most recent one.
+    pub fn events(&self) -> Vec<(u64, T)> {
+        self.lock().clone()
+    }
+
+    /// Returns the recorded events and removes them from the recording.
+    pub fn take(&self) -> Vec<(u64, T)> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// Writes all recorded events to `channel` at once, in the order they
+    /// were recorded.
+    ///
+    /// The events get new sequence numbers in `channel`. Replaying into the
+    /// channel the recorder is attached to records the events again.
+    pub fn replay_into(&self, channel: &mut EventChannel<T>) {
+        let events: Vec<_> = self.lock().iter().map(|(_, event)| event.clone()).collect();
+
+        channel.iter_write(events);
+    }
+
+    /// Returns the number of recorded events.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no events have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(u64, T)>> {
+        self.log.lock().expect("`Recorder` lock poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_replay() {
+        let mut channel = EventChannel::with_capacity(4);
+        let recorder = Recorder::attach(&mut channel);
+        channel.iter_write(0..3);
+        channel.set_ingest_filter(|e| if e % 2 == 0 { Some(e) } else { None });
+        channel.iter_write(3..7);
+        assert_eq!(recorder.events(), [(0, 0), (1, 1), (2, 2), (3, 4), (4, 6)]);
+        assert_eq!(recorder.len(), 5);
+
+        let mut replay = EventChannel::with_capacity(4);
+        let mut reader = replay.register_reader();
+        recorder.replay_into(&mut replay);
+        assert_eq!(replay.read(&mut reader).cloned().collect::<Vec<_>>(), [0, 1, 2, 4, 6]);
+
+        assert_eq!(recorder.take().len(), 5);
+        assert!(recorder.is_empty());
+        channel.single_write(8);
+        assert_eq!(recorder.events(), [(5, 8)]);
+
+        drop(recorder);
+        channel.iter_write(10..20);
+    }
+}
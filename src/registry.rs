@@ -0,0 +1,162 @@
+//! Keeping channels of different event types together.
+
+use std::{any::TypeId, collections::HashMap, fmt};
+
+use crate::{AnyChannel, Event, EventChannel, ReaderId};
+
+/// A collection of `EventChannel`s, at most one for every event type.
+///
+/// This is a simple event system: code only needs access to the registry to
+/// write events of any type, or to read them, and the channels are created
+/// on first use.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::ChannelRegistry;
+///
+/// struct Collision(u32, u32);
+///
+/// let mut registry = ChannelRegistry::new();
+/// let mut reader = registry.register_reader::<Collision>();
+///
+/// registry.single_write(Collision(1, 2));
+/// registry.single_write("ignored");
+///
+/// let channel = registry.get::<Collision>().unwrap();
+/// assert_eq!(channel.read(&mut reader).len(), 1);
+/// ```
+#[derive(Default)]
+pub struct ChannelRegistry {
+    channels: HashMap<TypeId, Box<dyn AnyChannel>>,
+}
+
+impl ChannelRegistry {
+    /// Create a new, empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the channel for events of type `E`, creating it with the
+    /// starting capacity `size` if there is none yet.
+    pub fn get_or_insert<E: Event>(&mut self, size: usize) -> &mut EventChannel<E> {
+        self.channels
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(EventChannel::<E>::with_capacity(size)))
+            .downcast_mut()
+            .expect("Bug in shrev: channel stored under the wrong `TypeId`")
+    }
+
+    /// Returns the channel for events of type `E`, creating it with the
+    /// default capacity of `EventChannel::new` if there is none yet.
+    pub fn channel_mut<E: Event>(&mut self) -> &mut EventChannel<E> {
+        self.channels
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(EventChannel::<E>::new()))
+            .downcast_mut()
+            .expect("Bug in shrev: channel stored under the wrong `TypeId`")
+    }
+
+    /// Write a single event into the channel for its type.
+    ///
+    /// See `channel_mut`.
+    pub fn single_write<E: Event>(&mut self, event: E) {
+        self.channel_mut().single_write(event);
+    }
+
+    /// Write an iterator of events into the channel for their type.
+    ///
+    /// See `channel_mut`.
+    pub fn iter_write<E, I>(&mut self, iter: I)
+    where
+        E: Event,
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.channel_mut().iter_write(iter);
+    }
+
+    /// Returns the channel for events of type `E`, if there is one.
+    pub fn get<E: Event>(&self) -> Option<&EventChannel<E>> {
+        self.channels
+            .get(&TypeId::of::<E>())
+            .and_then(|channel| channel.downcast_ref())
+    }
+
+    /// Returns the channel for events of type `E` mutably, if there is one.
+    pub fn get_mut<E: Event>(&mut self) -> Option<&mut EventChannel<E>> {
+        self.channels
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|channel| channel.downcast_mut())
+    }
+
+    /// Register a new reader for events of type `E`.
+    ///
+    /// See `channel_mut`.
+    pub fn register_reader<E: Event>(&mut self) -> ReaderId<E> {
+        self.channel_mut().register_reader()
+    }
+
+    /// Returns the number of channels in the registry.
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Checks if the registry has no channels.
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+}
+
+impl fmt::Debug for ChannelRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ChannelRegistry")
+            .field("channels", &self.channels.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolation() {
+        let mut registry = ChannelRegistry::new();
+        assert!(registry.get::<u32>().is_none());
+
+        let mut numbers = registry.register_reader::<u32>();
+        let mut strings = registry.register_reader::<String>();
+        registry.get_or_insert::<u32>(4).iter_write(0..3);
+        registry.get_or_insert(4).single_write("a".to_string());
+        assert_eq!(registry.len(), 2);
+
+        let channel = registry.get::<u32>().unwrap();
+        assert_eq!(channel.read(&mut numbers).cloned().collect::<Vec<_>>(), [0, 1, 2]);
+        let channel = registry.get::<String>().unwrap();
+        assert_eq!(channel.read(&mut strings).cloned().collect::<Vec<_>>(), ["a"]);
+
+        // The existing channel is kept, along with its readers
+        registry.get_mut::<u32>().unwrap().single_write(3);
+        let channel = registry.get_or_insert::<u32>(64);
+        assert_eq!(channel.read(&mut numbers).cloned().collect::<Vec<_>>(), [3]);
+        assert!(registry.get::<u64>().is_none());
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_lazy_channels() {
+        let mut registry = ChannelRegistry::new();
+        assert!(registry.is_empty());
+
+        registry.single_write(1u8);
+        let mut reader = registry.register_reader::<u8>();
+        registry.iter_write(vec![2u8, 3]);
+        registry.single_write('a');
+        assert_eq!(registry.len(), 2);
+
+        let channel = registry.channel_mut::<u8>();
+        assert_eq!(channel.read(&mut reader).cloned().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(registry.get::<char>().unwrap().iter_all().len(), 1);
+    }
+}
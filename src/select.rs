@@ -0,0 +1,246 @@
+//! Checking several channels for pending events at once.
+
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Wake, Waker},
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use crate::{Event, EventChannel, ReaderId, SyncEventChannel};
+
+/// A set of readers of channels with possibly different event types, which
+/// can be asked which of them have events pending.
+///
+/// Readers are added with `add` and `add_sync`, which return the index the
+/// reader is reported under. The `Select` borrows the channels and readers,
+/// so it is meant to be built right before checking them, e.g. once per
+/// frame.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::{EventChannel, Select};
+///
+/// let mut clicks = EventChannel::<(i32, i32)>::new();
+/// let mut keys = EventChannel::<char>::new();
+/// let click_reader = clicks.register_reader();
+/// let key_reader = keys.register_reader();
+///
+/// keys.single_write('q');
+///
+/// let mut select = Select::new();
+/// select.add(&clicks, &click_reader);
+/// select.add(&keys, &key_reader);
+/// assert_eq!(select.ready(), vec![1]);
+/// ```
+#[derive(Default)]
+pub struct Select<'a> {
+    entries: Vec<Box<dyn Entry + 'a>>,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty `Select`.
+    pub fn new() -> Self {
+        Select { entries: vec![] }
+    }
+
+    /// Adds `reader_id` of `channel`, returning its index.
+    pub fn add<E>(&mut self, channel: &'a EventChannel<E>, reader_id: &'a ReaderId<E>) -> usize
+    where
+        E: Event,
+    {
+        self.push(Box::new((channel, reader_id)))
+    }
+
+    /// Adds `reader_id` of `channel`, returning its index.
+    ///
+    /// Unlike the readers added with `add`, these can become ready while
+    /// waiting in `wait`.
+    pub fn add_sync<E>(
+        &mut self,
+        channel: &'a SyncEventChannel<E>,
+        reader_id: &'a ReaderId<E>,
+    ) -> usize
+    where
+        E: Event,
+    {
+        self.push(Box::new(SyncEntry(channel, reader_id)))
+    }
+
+    fn push(&mut self, entry: Box<dyn Entry + 'a>) -> usize {
+        self.entries.push(entry);
+
+        self.entries.len() - 1
+    }
+
+    /// Returns the indices of the readers which have events pending, in the
+    /// order they were added.
+    pub fn ready(&self) -> Vec<usize> {
+        self.indices(|entry| entry.has_pending())
+    }
+
+    /// Like `ready`, but parks the thread until some reader has events
+    /// pending, or until `timeout` has passed.
+    ///
+    /// Returns an empty `Vec` if no reader became ready in time. Only the
+    /// readers added with `add_sync` can become ready while waiting, and only
+    /// through writes to their `SyncEventChannel` which aren't done through
+    /// `SyncEventChannel::lock`. Without a `timeout` and without such a
+    /// write, this waits forever.
+    pub fn wait(&self, timeout: Option<Duration>) -> Vec<usize> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let waker = THREAD_WAKER.with(Waker::clone);
+
+        loop {
+            let ready = self.indices(|entry| entry.poll_pending(&waker));
+            if !ready.is_empty() {
+                return ready;
+            }
+
+            // Parking may also end spuriously, which the loop takes care of.
+            match deadline.map(|deadline| deadline.checked_duration_since(Instant::now())) {
+                None => thread::park(),
+                Some(Some(left)) => thread::park_timeout(left),
+                Some(None) => return ready,
+            }
+        }
+    }
+
+    fn indices<F>(&self, mut f: F) -> Vec<usize>
+    where
+        F: FnMut(&dyn Entry) -> bool,
+    {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| f(&***entry))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the number of readers added.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no readers have been added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").field("len", &self.len()).finish()
+    }
+}
+
+thread_local! {
+    // One waker per thread, so repeated waits don't pile up wakers in
+    // channels which are never written to.
+    static THREAD_WAKER: Waker = Waker::from(Arc::new(Unpark(thread::current())));
+}
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+trait Entry {
+    fn has_pending(&self) -> bool;
+
+    /// Like `has_pending`, but arranges for `waker` to be woken up by the
+    /// next write if there are no events pending.
+    fn poll_pending(&self, waker: &Waker) -> bool;
+}
+
+impl<E> Entry for (&EventChannel<E>, &ReaderId<E>)
+where
+    E: Event,
+{
+    fn has_pending(&self) -> bool {
+        self.0.reader_lag(self.1) > 0
+    }
+
+    fn poll_pending(&self, _: &Waker) -> bool {
+        // The channel is borrowed, so there won't be any writes to it.
+        self.has_pending()
+    }
+}
+
+struct SyncEntry<'a, E: 'static>(&'a SyncEventChannel<E>, &'a ReaderId<E>);
+
+impl<E> Entry for SyncEntry<'_, E>
+where
+    E: Event,
+{
+    fn has_pending(&self) -> bool {
+        self.0.lock().reader_lag(self.1) > 0
+    }
+
+    fn poll_pending(&self, waker: &Waker) -> bool {
+        self.0.poll_pending(self.1, waker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready() {
+        let mut numbers = EventChannel::new();
+        let mut strings = EventChannel::new();
+        let mut number_reader = numbers.register_reader();
+        let string_reader = strings.register_reader();
+        let sync = SyncEventChannel::new(EventChannel::new());
+        let sync_reader = sync.register_reader();
+
+        numbers.single_write(1);
+        sync.single_write(2.0);
+        {
+            let mut select = Select::new();
+            assert!(select.is_empty());
+            assert_eq!(select.add(&strings, &string_reader), 0);
+            assert_eq!(select.add_sync(&sync, &sync_reader), 1);
+            assert_eq!(select.add(&numbers, &number_reader), 2);
+            assert_eq!(select.ready(), [1, 2]);
+            assert_eq!(select.wait(None), [1, 2]);
+        }
+
+        numbers.read(&mut number_reader);
+        strings.single_write("event");
+        let mut select = Select::new();
+        select.add(&numbers, &number_reader);
+        select.add(&strings, &string_reader);
+        assert_eq!(select.ready(), [1]);
+    }
+
+    #[test]
+    fn test_wait() {
+        let channel = SyncEventChannel::new(EventChannel::new());
+        let mut reader = channel.register_reader();
+        let other = SyncEventChannel::new(EventChannel::<()>::new());
+        let other_reader = other.register_reader();
+
+        let mut select = Select::new();
+        select.add_sync(&other, &other_reader);
+        select.add_sync(&channel, &reader);
+        assert_eq!(select.wait(Some(Duration::from_millis(10))), []);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                channel.single_write(1);
+            });
+            assert_eq!(select.wait(None), [1]);
+        });
+        drop(select);
+        assert_eq!(channel.read_timeout(&mut reader, Duration::ZERO), [1]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Separate handles for writing to and reading from a channel.
+//!
+//! The handles share the channel through an `Rc<RefCell<_>>`, so they are
+//! meant to be used from a single thread and are neither `Send` nor `Sync`.
+//! Every call borrows the channel only for its duration, except `read_with`,
+//! during which writing panics.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{Event, EventChannel, EventIterator, ReaderId};
+
+/// Handle allowing to write events to a channel obtained from
+/// `EventChannel::split`.
+#[derive(Debug)]
+pub struct Writer<E> {
+    channel: Rc<RefCell<EventChannel<E>>>,
+}
+
+impl<E> Writer<E>
+where
+    E: Event,
+{
+    /// Write an iterator of events into storage
+    pub fn iter_write<I>(&self, iter: I)
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.channel.borrow_mut().iter_write(iter);
+    }
+
+    /// Drain a vector of events into storage.
+    pub fn drain_vec_write(&self, events: &mut Vec<E>) {
+        self.channel.borrow_mut().drain_vec_write(events);
+    }
+
+    /// Write a single event into storage.
+    pub fn single_write(&self, event: E) {
+        self.channel.borrow_mut().single_write(event);
+    }
+}
+
+/// Handle allowing to register readers and read events from a channel
+/// obtained from `EventChannel::split`.
+///
+/// Unlike the `Writer`, this handle can be cloned.
+#[derive(Debug)]
+pub struct ReaderFactory<E> {
+    channel: Rc<RefCell<EventChannel<E>>>,
+}
+
+impl<E> Clone for ReaderFactory<E> {
+    fn clone(&self) -> Self {
+        ReaderFactory {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<E> ReaderFactory<E>
+where
+    E: Event,
+{
+    /// Register a new reader.
+    ///
+    /// See `EventChannel::register_reader`.
+    pub fn register_reader(&self) -> ReaderId<E> {
+        self.channel.borrow_mut().register_reader()
+    }
+
+    /// Read the events pending for `reader_id` and pass them to `f`.
+    ///
+    /// The channel is borrowed while `f` runs, so `f` must not write to it.
+    pub fn read_with<F, R>(&self, reader_id: &mut ReaderId<E>, f: F) -> R
+    where
+        F: FnOnce(EventIterator<'_, E>) -> R,
+    {
+        f(self.channel.borrow().read(reader_id))
+    }
+}
+
+/// Moves `channel` behind the shared handles, see `EventChannel::split`.
+pub fn split<E>(channel: EventChannel<E>) -> (Writer<E>, ReaderFactory<E>) {
+    let channel = Rc::new(RefCell::new(channel));
+
+    (
+        Writer {
+            channel: channel.clone(),
+        },
+        ReaderFactory { channel },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        channel.single_write(0);
+
+        let (writer, factory) = channel.split();
+        let mut other_reader = factory.clone().register_reader();
+        writer.iter_write(1..3);
+
+        let read = |reader: &mut ReaderId<i32>| {
+            factory.read_with(reader, |events| events.cloned().collect::<Vec<_>>())
+        };
+        assert_eq!(read(&mut reader), vec![0, 1, 2]);
+        assert_eq!(read(&mut other_reader), vec![1, 2]);
+    }
+}
@@ -0,0 +1,275 @@
+//! Bounded ring buffer for exactly one writing and one reading thread.
+
+use std::{
+    cell::{Cell, UnsafeCell},
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    mask: usize,
+    /// Position of the next event to write; only changed by the writer.
+    write_pos: AtomicUsize,
+    /// Position of the next event to read; only changed by the reader.
+    read_pos: AtomicUsize,
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let (read, write) = (*self.read_pos.get_mut(), *self.write_pos.get_mut());
+        let mut pos = read;
+        while pos != write {
+            unsafe {
+                (*self.slots[pos & self.mask].get()).as_mut_ptr().drop_in_place();
+            }
+            pos = pos.wrapping_add(1);
+        }
+    }
+}
+
+// The slots between the two cursors belong to the reader, the others to the
+// writer; each cursor is only advanced by its owner.
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+/// Create a fixed-size ring buffer which can hold at least `size` events,
+/// returning the handles for writing to and reading from it.
+///
+/// Unlike `AtomicRingBuffer`, the events don't need to be `Copy`, and
+/// neither side ever waits for the other: every write and read finishes in
+/// a bounded number of steps. In exchange, there can only be one writer and
+/// one reader, which is enforced by the handles not being `Clone`. Like with
+/// `AtomicRingBuffer`, writing to a full buffer hands the event back.
+///
+/// This is why there is no loss reporting like `EventChannel::total_lost`:
+/// the reader never misses an event, since unread events are neither
+/// overwritten nor dropped. Events the writer gives up on after a rejected
+/// write are its own to count.
+///
+/// The size is rounded up to the next power of two.
+///
+/// ## Panics
+///
+/// Panics if `size` is zero.
+///
+/// ## Examples
+///
+/// ```
+/// use std::thread;
+///
+/// let (writer, reader) = shrev::spsc_channel(16);
+///
+/// let producer = thread::spawn(move || {
+///     for n in 0..3 {
+///         writer.single_write(n.to_string()).unwrap();
+///     }
+/// });
+/// producer.join().unwrap();
+///
+/// assert_eq!(reader.drain().collect::<Vec<_>>(), ["0", "1", "2"]);
+/// ```
+pub fn spsc_channel<T>(size: usize) -> (SpscWriter<T>, SpscReader<T>) {
+    assert!(size > 0, "`spsc_channel` needs a capacity");
+
+    let size = size.next_power_of_two();
+    let shared = Arc::new(Shared {
+        slots: (0..size)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        mask: size - 1,
+        write_pos: AtomicUsize::new(0),
+        read_pos: AtomicUsize::new(0),
+    });
+
+    (
+        SpscWriter {
+            shared: shared.clone(),
+            not_sync: PhantomData,
+        },
+        SpscReader {
+            shared,
+            not_sync: PhantomData,
+        },
+    )
+}
+
+/// Handle writing to a buffer obtained from `spsc_channel`.
+pub struct SpscWriter<T> {
+    shared: Arc<Shared<T>>,
+    /// Sharing the handle would allow writing from several threads.
+    not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> SpscWriter<T> {
+    /// Write a single event into the buffer.
+    ///
+    /// Returns the event back if the buffer is full.
+    pub fn single_write(&self, event: T) -> Result<(), T> {
+        let shared = &*self.shared;
+        let pos = shared.write_pos.load(Ordering::Relaxed);
+        // Pairs with the `Release` of the reader, so the slot has been read
+        // out before it gets overwritten.
+        let read = shared.read_pos.load(Ordering::Acquire);
+        if pos.wrapping_sub(read) == shared.slots.len() {
+            return Err(event);
+        }
+
+        unsafe {
+            (*shared.slots[pos & shared.mask].get()).as_mut_ptr().write(event);
+        }
+        shared.write_pos.store(pos.wrapping_add(1), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// The number of events the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.slots.len()
+    }
+}
+
+/// Handle reading from a buffer obtained from `spsc_channel`.
+pub struct SpscReader<T> {
+    shared: Arc<Shared<T>>,
+    /// Sharing the handle would allow reading from several threads.
+    not_sync: PhantomData<Cell<()>>,
+}
+
+impl<T> SpscReader<T> {
+    /// Read the oldest event which has not been read yet.
+    ///
+    /// Returns `None` if there is no such event.
+    pub fn read_single(&self) -> Option<T> {
+        let shared = &*self.shared;
+        let pos = shared.read_pos.load(Ordering::Relaxed);
+        // Pairs with the `Release` of the writer, so the event is visible.
+        if pos == shared.write_pos.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let event = unsafe { (*shared.slots[pos & shared.mask].get()).as_ptr().read() };
+        shared.read_pos.store(pos.wrapping_add(1), Ordering::Release);
+
+        Some(event)
+    }
+
+    /// Returns an iterator reading all events that are currently available.
+    pub fn drain(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.read_single())
+    }
+
+    /// Returns the number of events which are available to read.
+    pub fn len(&self) -> usize {
+        let read = self.shared.read_pos.load(Ordering::Relaxed);
+
+        self.shared.write_pos.load(Ordering::Acquire).wrapping_sub(read)
+    }
+
+    /// Checks if there are no events to read.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> fmt::Debug for SpscWriter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscWriter")
+            .field("capacity", &self.shared.slots.len())
+            .field("write_pos", &self.shared.write_pos)
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for SpscReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscReader")
+            .field("capacity", &self.shared.slots.len())
+            .field("read_pos", &self.shared.read_pos)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_full() {
+        let (writer, reader) = spsc_channel(2);
+        assert_eq!(writer.capacity(), 2);
+
+        assert_eq!(writer.single_write(1), Ok(()));
+        assert_eq!(writer.single_write(2), Ok(()));
+        assert_eq!(writer.single_write(3), Err(3));
+        assert_eq!(reader.len(), 2);
+
+        assert_eq!(reader.read_single(), Some(1));
+        assert_eq!(writer.single_write(3), Ok(()));
+        assert_eq!(reader.drain().collect::<Vec<_>>(), vec![2, 3]);
+        assert!(reader.is_empty());
+        assert_eq!(reader.read_single(), None);
+    }
+
+    #[test]
+    fn test_single_slot() {
+        let (writer, reader) = spsc_channel(1);
+        assert_eq!(writer.capacity(), 1);
+
+        for n in 0..3 {
+            assert_eq!(writer.single_write(n), Ok(()));
+            assert_eq!(writer.single_write(n + 1), Err(n + 1));
+            assert_eq!(reader.read_single(), Some(n));
+            assert_eq!(reader.read_single(), None);
+        }
+    }
+
+    #[test]
+    fn test_drops_unread() {
+        let event = Arc::new(());
+        let (writer, reader) = spsc_channel(4);
+        for _ in 0..3 {
+            writer.single_write(event.clone()).unwrap();
+        }
+        drop(reader.read_single());
+        assert_eq!(Arc::strong_count(&event), 3);
+
+        drop((writer, reader));
+        assert_eq!(Arc::strong_count(&event), 1);
+    }
+
+    #[test]
+    fn test_threads() {
+        let (writer, reader) = spsc_channel(8);
+
+        let producer = thread::spawn(move || {
+            for n in 0..10_000 {
+                let mut event = n.to_string();
+                while let Err(rejected) = writer.single_write(event) {
+                    event = rejected;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut next = 0;
+        while next < 10_000 {
+            match reader.read_single() {
+                Some(event) => {
+                    assert_eq!(event, next.to_string());
+                    next += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(reader.read_single(), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Channels which record when and in which order events were written.
+
+use std::time::Instant;
+
+use crate::{Event, EventChannel, EventIterator, ReaderId};
+
+/// An event along with metadata recorded when it was written to a
+/// `StampedChannel`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stamped<T> {
+    /// The sequence number of the event, see `EventChannel::next_sequence`.
+    pub sequence: u64,
+    /// When the event was written.
+    pub time: Instant,
+    /// The event itself.
+    pub event: T,
+}
+
+/// A channel which stamps every event with its sequence number and the time
+/// it was written.
+///
+/// This helps with debugging the order in which different parts of a
+/// program write and read events.
+///
+/// ## Examples
+///
+/// ```
+/// use shrev::StampedChannel;
+///
+/// let mut channel = StampedChannel::new();
+/// let mut reader = channel.register_reader();
+///
+/// channel.single_write("spawn");
+/// channel.iter_write(vec!["move", "despawn"]);
+///
+/// let events: Vec<_> = channel.read(&mut reader).map(|s| (s.sequence, s.event)).collect();
+/// assert_eq!(events, vec![(0, "spawn"), (1, "move"), (2, "despawn")]);
+/// ```
+#[derive(Debug)]
+pub struct StampedChannel<T> {
+    channel: EventChannel<Stamped<T>>,
+}
+
+impl<T> StampedChannel<T>
+where
+    T: Event,
+{
+    /// Create a new `StampedChannel` with a default size of 64.
+    pub fn new() -> Self {
+        StampedChannel {
+            channel: EventChannel::new(),
+        }
+    }
+
+    /// Create a new `StampedChannel` with the given starting capacity.
+    pub fn with_capacity(size: usize) -> Self {
+        StampedChannel {
+            channel: EventChannel::with_capacity(size),
+        }
+    }
+
+    pub(crate) fn from_channel(channel: EventChannel<Stamped<T>>) -> Self {
+        StampedChannel { channel }
+    }
+
+    /// Write a single event, stamped with the current time.
+    pub fn single_write(&mut self, event: T) {
+        self.iter_write(Some(event));
+    }
+
+    /// Write an iterator of events, all stamped with the same time.
+    pub fn iter_write<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let (first, time) = (self.channel.next_sequence(), Instant::now());
+        let stamped = iter.into_iter().enumerate().map(|(n, event)| Stamped {
+            sequence: first + n as u64,
+            time,
+            event,
+        });
+
+        self.channel.iter_write(stamped);
+    }
+
+    /// Register a new reader.
+    ///
+    /// See `EventChannel::register_reader`.
+    pub fn register_reader(&mut self) -> ReaderId<Stamped<T>> {
+        self.channel.register_reader()
+    }
+
+    /// Read the events written since the last read with `reader_id`.
+    ///
+    /// See `EventChannel::read`.
+    pub fn read(&self, reader_id: &mut ReaderId<Stamped<T>>) -> EventIterator<'_, Stamped<T>> {
+        self.channel.read(reader_id)
+    }
+
+    /// Returns the underlying channel.
+    pub fn into_inner(self) -> EventChannel<Stamped<T>> {
+        self.channel
+    }
+}
+
+impl<T> Default for StampedChannel<T>
+where
+    T: Event,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamps() {
+        let mut channel = StampedChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+        let before = Instant::now();
+
+        channel.iter_write(0..3);
+        channel.iter_write(None);
+        channel.single_write(3);
+        let read: Vec<_> = channel.read(&mut reader).cloned().collect();
+
+        let sequences: Vec<_> = read.iter().map(|s| (s.sequence, s.event)).collect();
+        assert_eq!(sequences, [(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert_eq!(read[0].time, read[2].time);
+        assert!(read[0].time >= before && read[3].time >= read[2].time);
+        assert_eq!(channel.into_inner().next_sequence(), 4);
+    }
+}
@@ -0,0 +1,118 @@
+//! Counters for observing the usage of an `EventChannel`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Cumulative statistics of an `EventChannel`.
+///
+/// Obtained with `EventChannel::stats`; all numbers are counted since the
+/// creation of the channel or the last call to `EventChannel::reset_stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of writes which added at least one event.
+    pub writes: u64,
+    /// Number of reads, with either a `ReaderId` or a `Cursor`.
+    pub reads: u64,
+    /// Number of events written.
+    pub events_written: u64,
+    /// Number of events returned by reads.
+    pub events_read: u64,
+    /// Number of events skipped by cursors because they were overwritten
+    /// before being read.
+    pub events_lost: u64,
+    /// Number of events which were not written because of
+    /// `DropPolicy::DropNewest`.
+    pub events_dropped: u64,
+    /// Number of events which were removed by `EventChannel::expire_before`,
+    /// whether or not some reader had them pending.
+    pub events_expired: u64,
+    /// Number of cursor reads which skipped events and writes which dropped
+    /// events, i.e. how often `events_lost` or `events_dropped` grew.
+    pub loss_incidents: u64,
+}
+
+/// A summary of how far behind the readers of an `EventChannel` are.
+///
+/// Obtained with `EventChannel::health`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferHealth {
+    /// No reader has any events pending.
+    Idle,
+    /// Some events are pending, but there is plenty of room for more.
+    Nominal,
+    /// The slowest reader has at least three quarters of the capacity of the
+    /// channel pending.
+    NearFull,
+    /// The slowest reader has as many events pending as the channel can
+    /// hold, so the next write will make it grow (or, with
+    /// `DropPolicy::DropNewest`, be dropped).
+    Overflowing,
+}
+
+impl BufferHealth {
+    pub(crate) fn from_lag(lag: usize, capacity: usize) -> Self {
+        match lag {
+            0 => BufferHealth::Idle,
+            lag if lag >= capacity => BufferHealth::Overflowing,
+            lag if lag >= capacity - capacity / 4 => BufferHealth::NearFull,
+            _ => BufferHealth::Nominal,
+        }
+    }
+}
+
+/// The counters behind `Stats`, which can be updated through a shared
+/// reference.
+#[derive(Debug, Default)]
+pub struct StatsCounters {
+    writes: AtomicU64,
+    reads: AtomicU64,
+    events_written: AtomicU64,
+    events_read: AtomicU64,
+    events_lost: AtomicU64,
+    events_dropped: AtomicU64,
+    events_expired: AtomicU64,
+    loss_incidents: AtomicU64,
+}
+
+impl StatsCounters {
+    pub fn record_write(&mut self, events: usize) {
+        *self.writes.get_mut() += 1;
+        *self.events_written.get_mut() += events as u64;
+    }
+
+    pub fn record_drop(&mut self, events: usize) {
+        if events > 0 {
+            *self.events_dropped.get_mut() += events as u64;
+            *self.loss_incidents.get_mut() += 1;
+        }
+    }
+
+    pub fn record_expiry(&mut self, events: usize) {
+        *self.events_expired.get_mut() += events as u64;
+    }
+
+    pub fn record_read(&self, events: usize, lost: u64) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        self.events_read.fetch_add(events as u64, Ordering::Relaxed);
+        if lost > 0 {
+            self.events_lost.fetch_add(lost, Ordering::Relaxed);
+            self.loss_incidents.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self) -> Stats {
+        Stats {
+            writes: self.writes.load(Ordering::Relaxed),
+            reads: self.reads.load(Ordering::Relaxed),
+            events_written: self.events_written.load(Ordering::Relaxed),
+            events_read: self.events_read.load(Ordering::Relaxed),
+            events_lost: self.events_lost.load(Ordering::Relaxed),
+            events_dropped: self.events_dropped.load(Ordering::Relaxed),
+            events_expired: self.events_expired.load(Ordering::Relaxed),
+            loss_incidents: self.loss_incidents.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
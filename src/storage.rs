@@ -2,15 +2,29 @@
 
 use std::{
     cell::UnsafeCell,
+    cmp::Ordering,
+    collections::VecDeque,
     fmt,
+    hash::{Hash, Hasher},
+    iter::{self, FusedIterator},
     marker::PhantomData,
+    mem,
     num::Wrapping,
     ops::{Add, AddAssign, Sub, SubAssign},
     ptr,
-    sync::mpsc::{self, Receiver, Sender},
+    sync::{
+        atomic,
+        mpsc::{self, Receiver, Sender},
+        Weak,
+    },
 };
 
-use crate::util::{InstanceId, NoSharedAccess, Reference};
+use crate::{
+    dump::{ChannelDump, ReaderDump},
+    filtered::LossFilter,
+    stats::{Stats, StatsCounters},
+    util::{AtomicU64, InstanceId, NoSharedAccess, Reference, SharedArc as Arc},
+};
 use std::fmt::Debug;
 
 #[derive(Clone, Copy, Debug)]
@@ -65,7 +79,12 @@ impl Add<usize> for CircularIndex {
     type Output = usize;
 
     fn add(self, rhs: usize) -> usize {
-        (self.index + rhs) % self.size
+        // Avoids overflowing for sizes close to `usize::MAX`
+        let rhs = rhs % self.size;
+        match self.size - rhs {
+            x if self.index >= x => self.index - x,
+            _ => self.index + rhs,
+        }
     }
 }
 
@@ -79,7 +98,11 @@ impl Sub<usize> for CircularIndex {
     type Output = usize;
 
     fn sub(self, rhs: usize) -> usize {
-        (self.size - rhs + self.index) % self.size
+        let rhs = rhs % self.size;
+        match self.index >= rhs {
+            true => self.index - rhs,
+            false => self.index + (self.size - rhs),
+        }
     }
 }
 
@@ -114,54 +137,140 @@ impl<T> Data<T> {
         self.data.get_unchecked(index)
     }
 
-    unsafe fn put(&mut self, cursor: usize, elem: T) {
+    unsafe fn get_mut(&mut self, index: usize) -> &mut T {
+        self.data.get_unchecked_mut(index)
+    }
+
+    /// Stores `elem` under `cursor`, returning the element it replaces.
+    unsafe fn put(&mut self, cursor: usize, elem: T) -> Option<T> {
         if self.uninitialized > 0 {
             // There is no element stored under `cursor`
             // -> do not drop anything!
             ptr::write(self.data.get_unchecked_mut(cursor) as *mut T, elem);
             self.uninitialized -= 1;
+
+            None
         } else {
-            // We can safely drop this, it's initialized.
-            *self.data.get_unchecked_mut(cursor) = elem;
+            // It's initialized, so we can safely take it out.
+            Some(mem::replace(self.data.get_unchecked_mut(cursor), elem))
         }
     }
 
+    /// Copies `elems` to the positions starting at `cursor`, wrapping around
+    /// the end. There must not be more of them than positions.
+    unsafe fn copy_from(&mut self, cursor: usize, elems: &[T])
+    where
+        T: Copy,
+    {
+        let size = self.data.len();
+        debug_assert!(elems.len() <= size, "Bug in shrev");
+        let first = elems.len().min(size - cursor);
+        let dst = self.data.as_mut_ptr();
+        ptr::copy_nonoverlapping(elems.as_ptr(), dst.add(cursor), first);
+        ptr::copy_nonoverlapping(elems.as_ptr().add(first), dst, elems.len() - first);
+        // The uninitialized positions are the ones right after the last
+        // element, so they are initialized first.
+        self.uninitialized = self.uninitialized.saturating_sub(elems.len());
+    }
+
     /// `cursor` is the first position that gets moved to the back,
     /// free memory will be created between `cursor - 1` and `cursor`.
     unsafe fn grow(&mut self, cursor: usize, by: usize) {
-        assert!(by >= self.data.len());
-
         // Calculate how many elements we need to move
         let to_move = self.data.len() - cursor;
 
         // Reserve space and set the new length
+        let new = self.data.len().checked_add(by).expect("capacity overflow");
         self.data.reserve_exact(by);
-        let new = self.data.len() + by;
         self.data.set_len(new);
 
         // Move the elements after the cursor to the end of the buffer.
-        // Since we grew the buffer at least by the old length,
-        // the elements are non-overlapping.
-        let src = self.data.as_ptr().offset(cursor as isize);
-        let dst = self.data.as_mut_ptr().offset((cursor + by) as isize);
-        ptr::copy_nonoverlapping(src, dst, to_move);
+        // Unless we grew the buffer at least by the old length, the
+        // elements may overlap.
+        let src = self.data.as_ptr().add(cursor);
+        let dst = self.data.as_mut_ptr().add(cursor + by);
+        ptr::copy(src, dst, to_move);
 
         self.uninitialized += by;
     }
 
     /// Called when dropping the ring buffer.
     unsafe fn clean(&mut self, cursor: usize) {
-        let mut cursor = CircularIndex::new(cursor, self.data.len());
-        let end = cursor - 1;
-
-        while let Some(i) = cursor.step(end) {
-            if self.uninitialized > 0 {
-                self.uninitialized -= 1;
-            } else {
-                ptr::drop_in_place(self.data.get_unchecked_mut(i) as *mut T);
+        if mem::needs_drop::<T>() && self.num_initialized() > 0 {
+            // The uninitialized elements are the ones right after `cursor`.
+            let mut cursor = CircularIndex::new(cursor, self.data.len());
+            cursor += self.uninitialized;
+
+            for _ in 0..self.num_initialized() {
+                ptr::drop_in_place(self.data.get_unchecked_mut(cursor.index) as *mut T);
+                cursor += 1;
             }
         }
 
+        self.uninitialized = 0;
+        self.data.set_len(0);
+    }
+
+    /// Returns `len` elements starting at `start`, which must all be
+    /// initialized.
+    unsafe fn slice(&self, start: usize, len: usize) -> &[T] {
+        std::slice::from_raw_parts(self.data.as_ptr().add(start), len)
+    }
+
+    /// Rotates all slots, initialized or not, `mid` positions to the left.
+    unsafe fn rotate_left(&mut self, mid: usize) {
+        // Viewing the slots as `MaybeUninit` avoids moving uninitialized
+        // memory around as `T`.
+        let slots = std::slice::from_raw_parts_mut(
+            self.data.as_mut_ptr() as *mut mem::MaybeUninit<T>,
+            self.data.len(),
+        );
+        slots.rotate_left(mid);
+    }
+
+    /// Drops the `num` elements at the start, which must be initialized, and
+    /// removes slots from the end until there are `size` left. Only
+    /// uninitialized slots may be removed that way.
+    unsafe fn shrink(&mut self, num: usize, size: usize) {
+        for index in 0..num {
+            ptr::drop_in_place(self.data.get_unchecked_mut(index) as *mut T);
+        }
+        self.rotate_left(num);
+
+        let removed = self.data.len() - size;
+        debug_assert!(self.uninitialized + num >= removed, "Bug in shrev");
+        self.uninitialized = self.uninitialized + num - removed;
+        self.data.set_len(size);
+        self.data.shrink_to_fit();
+    }
+
+    /// Drops the `num` oldest elements, which must be initialized, where
+    /// `cursor` is the slot right after the most recent one.
+    unsafe fn drop_oldest(&mut self, cursor: usize, num: usize) {
+        // The uninitialized slots are the ones right before the oldest element.
+        let mut cursor = CircularIndex::new(cursor, self.data.len());
+        cursor += self.uninitialized;
+        for _ in 0..num {
+            ptr::drop_in_place(self.data.get_unchecked_mut(cursor.index) as *mut T);
+            cursor += 1;
+        }
+
+        self.uninitialized += num;
+    }
+
+    /// Drops all elements and makes room for `size` uninitialized ones,
+    /// reusing the allocation if possible.
+    unsafe fn reset(&mut self, cursor: usize, size: usize) {
+        self.clean(cursor);
+        self.data.reserve_exact(size);
+        self.data.set_len(size);
+        self.uninitialized = size;
+    }
+
+    /// Forgets all elements without dropping them, after they have been
+    /// moved out.
+    unsafe fn forget(&mut self) {
+        self.uninitialized = 0;
         self.data.set_len(0);
     }
 
@@ -183,6 +292,13 @@ impl<T: Debug> Debug for Data<T> {
 struct Reader {
     generation: usize,
     last_index: usize,
+    /// Number of elements written when this reader last read.
+    read_at: u64,
+    /// Number of elements dropped when this reader was registered.
+    dropped_at: u64,
+    /// Maximum number of the most recent elements this reader reads; older
+    /// ones are skipped.
+    max_age: usize,
 }
 
 impl Reader {
@@ -206,6 +322,32 @@ impl Reader {
         }
     }
 
+    /// Returns the number of elements this reader has not read yet.
+    fn pending(&self, last: CircularIndex, current_gen: usize) -> usize {
+        last.size - self.distance_from(last, current_gen)
+    }
+
+    /// Returns the number of pending elements which are older than
+    /// `max_age`, and get skipped.
+    fn stale(&self, last: CircularIndex, current_gen: usize) -> usize {
+        self.pending(last, current_gen).saturating_sub(self.max_age)
+    }
+
+    /// Moves this reader forward by `num` elements, which must not be more
+    /// than it has pending.
+    fn advance(&mut self, num: usize, last: CircularIndex, current_gen: usize) {
+        let pending = self.pending(last, current_gen);
+        debug_assert!(num <= pending, "Bug in shrev");
+
+        if num == pending {
+            self.last_index = last.index;
+            self.generation = current_gen;
+        } else {
+            // The generation stays outdated, marking the reader as behind.
+            self.last_index = CircularIndex::new(self.last_index, last.size) + num;
+        }
+    }
+
     fn needs_shift(&self, last_index: usize, current_gen: usize) -> bool {
         self.last_index > last_index
             || (self.last_index == last_index && self.generation != current_gen)
@@ -224,9 +366,19 @@ impl Reader {
 /// Note that as long as a `ReaderId` exists, it is crucial to use it to read
 /// the events; otherwise the buffer of the `EventChannel` **will** keep
 /// growing.
+///
+/// Two `ReaderId`s are equal if they refer to the same reader of the same
+/// channel. Reading doesn't change that, so a `ReaderId` can be used as a key
+/// in maps.
+///
+/// A reader can be given a label, which shows up in its `Debug` output. The
+/// label is ignored when comparing or hashing readers.
 pub struct ReaderId<T: 'static> {
     id: usize,
-    marker: PhantomData<&'static [T]>,
+    label: Option<&'static str>,
+    // Only used to tell readers of different event types apart, so it
+    // mustn't affect the auto traits.
+    marker: PhantomData<fn() -> T>,
     reference: Reference,
     // stupid way to make this `Sync`
     drop_notifier: NoSharedAccess<Sender<usize>>,
@@ -236,6 +388,7 @@ impl<T: 'static> fmt::Debug for ReaderId<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ReaderId")
             .field("id", &self.id)
+            .field("label", &self.label)
             .field("marker", &self.marker)
             .field("reference", &self.reference)
             .field("drop_notifier", &self.drop_notifier)
@@ -243,12 +396,198 @@ impl<T: 'static> fmt::Debug for ReaderId<T> {
     }
 }
 
+impl<T: 'static> ReaderId<T> {
+    /// Returns the number identifying this reader within its channel.
+    ///
+    /// Numbers of dropped readers get reused for new ones.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the label this reader was registered with, if any.
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+}
+
+impl<T: 'static> PartialEq for ReaderId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.reference == other.reference
+    }
+}
+
+impl<T: 'static> Eq for ReaderId<T> {}
+
+impl<T: 'static> Hash for ReaderId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.reference.hash(state);
+    }
+}
+
 impl<T: 'static> Drop for ReaderId<T> {
     fn drop(&mut self) {
         let _ = self.drop_notifier.get_mut().send(self.id);
     }
 }
 
+/// A reader whose position is an atomic sequence number, so reading with it
+/// only needs a shared reference.
+///
+/// Like a `ReaderId`, it keeps the channel from overwriting the events it
+/// hasn't read yet. Every read takes all events written since the previous
+/// read, so concurrent reads through the same `AtomicReaderId` split the
+/// events between them: each event is read exactly once.
+///
+/// Dropping an `AtomicReaderId` unregisters it, the next time the channel
+/// needs to know about its readers.
+pub struct AtomicReaderId<T: 'static> {
+    /// The sequence number of the next element to read.
+    position: Arc<AtomicU64>,
+    // Like `ReaderId::marker`, this mustn't affect the auto traits.
+    marker: PhantomData<fn() -> T>,
+    reference: Reference,
+}
+
+impl<T: 'static> fmt::Debug for AtomicReaderId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicReaderId")
+            .field("position", &self.position)
+            .field("reference", &self.reference)
+            .finish()
+    }
+}
+
+/// A lightweight position in the event stream of an `EventChannel`.
+///
+/// Unlike a `ReaderId`, a `Cursor` is not registered with the channel: it is
+/// just a counter of the events it has seen, so it can be copied and stored
+/// anywhere. Reading with it skips the check that the cursor belongs to the
+/// channel, which is safe because the position is validated against the
+/// events the channel still holds.
+///
+/// Since the channel doesn't know about cursors, it won't keep events around
+/// for them. A cursor that falls behind by more than the capacity of the
+/// channel misses the overwritten events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub(crate) written: u64,
+}
+
+/// Refers to a single element written to a ring buffer by its sequence
+/// number.
+///
+/// In contrast to a position in the buffer, the sequence number stays valid
+/// when the buffer grows or gets compacted. Once the element is overwritten,
+/// it no longer resolves to anything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventHandle {
+    sequence: u64,
+}
+
+impl EventHandle {
+    /// Returns the sequence number of the element.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+/// Refers to the elements written by one write by their sequence numbers,
+/// from `start` up to, but not including, `end`.
+///
+/// Like with an `EventHandle`, the range stays valid when the buffer grows
+/// or gets compacted, but its elements are eventually overwritten.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EventRange {
+    start: u64,
+    end: u64,
+}
+
+impl EventRange {
+    /// Returns the sequence number of the first element.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the sequence number after the last element.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Returns the number of elements in the range.
+    pub fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    /// Checks if the range contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Refers to the elements a reader got from `read_uncommitted`, so it can be
+/// advanced past them later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommitToken {
+    reader: usize,
+    start: u64,
+    len: usize,
+}
+
+impl CommitToken {
+    /// Returns the number of elements the token commits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the token commits no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// The error returned when committing a `CommitToken` which no longer
+/// matches the position of the reader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleCommit;
+
+impl fmt::Display for StaleCommit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the reader has moved since the commit token was created")
+    }
+}
+
+impl std::error::Error for StaleCommit {}
+
+/// A position of a reader, which it can be moved back to with
+/// `EventChannel::restore`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReaderCheckpoint {
+    reader: usize,
+    sequence: u64,
+}
+
+impl ReaderCheckpoint {
+    /// Returns the sequence number of the first element the reader had
+    /// pending when the checkpoint was created.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+/// The error returned when restoring a `ReaderCheckpoint` whose elements are
+/// no longer stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckpointExpired;
+
+impl fmt::Display for CheckpointExpired {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the elements after the checkpoint have been overwritten")
+    }
+}
+
+impl std::error::Error for CheckpointExpired {}
+
 #[derive(Default)]
 struct ReaderMeta {
     /// Free ids
@@ -261,6 +600,8 @@ impl ReaderMeta {
         Default::default()
     }
 
+    // Taking `&mut ReaderId` guarantees exclusive access to this reader's state.
+    #[allow(clippy::mut_from_ref)]
     fn reader<T>(&self, id: &mut ReaderId<T>) -> Option<&mut Reader> {
         self.readers.get(id.id).map(|r| unsafe { &mut *r.get() })
     }
@@ -280,20 +621,16 @@ impl ReaderMeta {
             .any(|r| r.active())
     }
 
-    fn alloc(&mut self, last_index: usize, generation: usize) -> usize {
+    fn alloc(&mut self, reader: Reader) -> usize {
         match self.free.pop() {
             Some(id) => {
-                self.reader_exclusive(id).last_index = last_index;
-                self.reader_exclusive(id).generation = generation;
+                *self.reader_exclusive(id) = reader;
 
                 id
             }
             None => {
                 let id = self.readers.len();
-                self.readers.push(UnsafeCell::new(Reader {
-                    generation,
-                    last_index,
-                }));
+                self.readers.push(UnsafeCell::new(reader));
 
                 id
             }
@@ -314,6 +651,14 @@ impl ReaderMeta {
             .min_by_key(|reader| reader.distance_from(last, current_gen))
     }
 
+    fn farthest_index(&mut self, last: CircularIndex, current_gen: usize) -> Option<&Reader> {
+        self.readers
+            .iter()
+            .map(|reader| unsafe { &*reader.get() })
+            .filter(|reader| reader.active())
+            .max_by_key(|reader| reader.distance_from(last, current_gen))
+    }
+
     fn shift(&mut self, last_index: usize, current_gen: usize, grow_by: usize) {
         for reader in &mut self.readers {
             let reader = unsafe { &mut *reader.get() } as &mut Reader;
@@ -331,6 +676,32 @@ impl ReaderMeta {
 unsafe impl Send for ReaderMeta {}
 unsafe impl Sync for ReaderMeta {}
 
+type RecycleSink<T> = Box<dyn FnMut(T) + Send>;
+type Tap<T> = Box<dyn FnMut(&T) + Send>;
+type EvictionCallback = Box<dyn FnOnce() + Send>;
+type IngestFilter<T> = Box<dyn FnMut(T) -> Option<T> + Send>;
+// Called through shared references by cursor reads, hence `Fn` and `Sync`.
+type LossHook = Box<dyn Fn(u64) + Send + Sync>;
+type LagAlarm = Box<dyn FnMut(usize, usize) + Send>;
+
+/// Decides what happens to a write which doesn't fit into the ring buffer
+/// without overwriting elements that have not been read by every reader.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Grow the ring buffer, so every reader receives every element.
+    ///
+    /// This is the default.
+    #[default]
+    Grow,
+    /// Keep the size of the ring buffer and drop the elements which don't
+    /// fit, starting with the most recent one.
+    ///
+    /// This keeps the oldest unread elements. To get a rejected element
+    /// back, write it with `EventChannel::try_single_write`; `write_lossy`
+    /// only returns how many were dropped.
+    DropNewest,
+}
+
 /// Ring buffer, holding data of type `T`.
 pub struct RingBuffer<T> {
     available: usize,
@@ -341,11 +712,43 @@ pub struct RingBuffer<T> {
     generation: Wrapping<usize>,
     instance_id: InstanceId,
     meta: ReaderMeta,
+    /// Total number of events written so far.
+    written: u64,
+    /// Total number of events dropped because of the `DropPolicy`.
+    dropped: u64,
+    stats: StatsCounters,
+    empty_write_hook: Option<NoSharedAccess<Box<dyn FnMut() + Send>>>,
+    loss_hook: Option<LossHook>,
+    /// The lag above which readers trigger the alarm.
+    lag_alarm: Option<(usize, NoSharedAccess<LagAlarm>)>,
+    recycle_sink: Option<NoSharedAccess<RecycleSink<T>>>,
+    taps: Vec<NoSharedAccess<Tap<T>>>,
+    policy: DropPolicy,
+    /// The maximum age of readers created with `new_reader_id`.
+    reader_max_age: usize,
+    /// The positions of the `AtomicReaderId`s, and the ids of the readers
+    /// which keep their elements stored.
+    atomic_readers: Vec<(Arc<AtomicU64>, usize)>,
+    /// The filters of the filtered readers, which count the dropped elements
+    /// matching them.
+    loss_filters: Vec<Weak<LossFilter<T>>>,
+    /// Elements written while paused, in the order they were written.
+    paused: Option<Vec<T>>,
+    /// Callbacks for when the element with the sequence number is no longer
+    /// stored, ordered by the sequence number.
+    evictions: NoSharedAccess<VecDeque<(u64, EvictionCallback)>>,
+    ingest_filter: Option<NoSharedAccess<IngestFilter<T>>>,
 }
 
 impl<T: 'static> RingBuffer<T> {
     /// Create a new ring buffer with the given max size.
     pub fn new(size: usize) -> Self {
+        Self::with_policy(size, DropPolicy::default())
+    }
+
+    /// Create a new ring buffer with the given starting size, handling
+    /// overflowing writes according to `policy`.
+    pub fn with_policy(size: usize, policy: DropPolicy) -> Self {
         assert!(size > 1);
 
         let (free_tx, free_rx) = mpsc::channel();
@@ -361,6 +764,21 @@ impl<T: 'static> RingBuffer<T> {
             generation: Wrapping(0),
             instance_id: InstanceId::new("`ReaderId` was not allocated by this `EventChannel`"),
             meta: ReaderMeta::new(),
+            written: 0,
+            dropped: 0,
+            stats: StatsCounters::default(),
+            empty_write_hook: None,
+            loss_hook: None,
+            lag_alarm: None,
+            recycle_sink: None,
+            taps: vec![],
+            policy,
+            reader_max_age: usize::MAX,
+            atomic_readers: vec![],
+            loss_filters: vec![],
+            paused: None,
+            evictions: NoSharedAccess::new(VecDeque::new()),
+            ingest_filter: None,
         }
     }
 
@@ -371,20 +789,272 @@ impl<T: 'static> RingBuffer<T> {
         I::IntoIter: ExactSizeIterator,
     {
         let iter = iter.into_iter();
+        match &mut self.ingest_filter {
+            Some(filter) if iter.len() > 0 => {
+                let kept: Vec<_> = iter.filter_map(filter.get_mut()).collect();
+                // Writing nothing because of the filter is no empty write.
+                if !kept.is_empty() {
+                    self.write_unfiltered(kept.into_iter());
+                }
+            }
+            _ => self.write_unfiltered(iter),
+        }
+    }
+
+    fn write_unfiltered<I>(&mut self, mut iter: I)
+    where
+        I: ExactSizeIterator<Item = T>,
+    {
         let len = iter.len();
-        if len > 0 {
-            self.ensure_additional(len);
-            for element in iter {
-                unsafe {
-                    self.data.put(self.last_index + 1, element);
+        if let (Some(paused), true) = (&mut self.paused, len > 0) {
+            paused.extend(iter);
+        } else if len > 0 {
+            let len = self.reserve(len);
+
+            for element in iter.by_ref().take(len) {
+                for tap in &mut self.taps {
+                    (tap.get_mut())(&element);
+                }
+                let old = unsafe { self.data.put(self.last_index + 1, element) };
+                if let (Some(old), Some(sink)) = (old, &mut self.recycle_sink) {
+                    (sink.get_mut())(old);
                 }
                 self.last_index += 1;
             }
-            self.available -= len;
-            self.generation += Wrapping(1);
+            // Whatever is left didn't fit and is dropped.
+            self.record_filtered_drops(iter);
+            if len > 0 {
+                self.finish_write(len);
+            }
+        } else if let Some(hook) = &mut self.empty_write_hook {
+            // An empty write deliberately leaves all state untouched, it
+            // doesn't even count as a write in the statistics.
+            (hook.get_mut())();
+        }
+    }
+
+    /// Pushes copies of the elements of `data` to the buffer.
+    ///
+    /// Unless there are taps, an ingest filter or a recycle sink, or writing
+    /// is paused, the elements are copied in at most two chunks instead of
+    /// one by one.
+    pub fn copy_write(&mut self, data: &[T])
+    where
+        T: Copy,
+    {
+        let plain = self.paused.is_none()
+            && self.ingest_filter.is_none()
+            && self.taps.is_empty()
+            && self.recycle_sink.is_none()
+            && self.loss_filters.is_empty();
+        if !plain || data.is_empty() {
+            return self.iter_write(data.iter().copied());
+        }
+
+        let len = self.reserve(data.len());
+        if len == 0 {
+            return;
+        }
+        // Without readers, the oldest elements would be overwritten by the
+        // newest ones of the same write anyway.
+        let skip = len.saturating_sub(self.last_index.size);
+        unsafe {
+            self.data.copy_from(self.last_index + (1 + skip), &data[skip..len]);
+        }
+        self.last_index += len;
+        self.finish_write(len);
+    }
+
+    /// Makes room for `len` elements according to the `DropPolicy`, and
+    /// returns how many of them can be written.
+    /// Counts the dropped `elements` for the filtered readers they match.
+    fn record_filtered_drops<I: Iterator<Item = T>>(&mut self, elements: I) {
+        self.loss_filters.retain(|filter| filter.strong_count() > 0);
+        if self.loss_filters.is_empty() {
+            return;
+        }
+
+        let filters: Vec<_> = self.loss_filters.iter().filter_map(Weak::upgrade).collect();
+        for element in elements {
+            for filter in &filters {
+                if (filter.filter)(&element) {
+                    filter.lost.fetch_add(1, atomic::Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Registers the filter of a filtered reader, which counts the dropped
+    /// elements matching it as long as the reader is alive.
+    pub(crate) fn add_loss_filter(&mut self, filter: Weak<LossFilter<T>>) {
+        self.loss_filters.push(filter);
+    }
+
+    fn reserve(&mut self, len: usize) -> usize {
+        match self.policy {
+            DropPolicy::Grow => {
+                self.ensure_additional(len);
+
+                len
+            }
+            DropPolicy::DropNewest => {
+                let kept = self.reserve_up_to(len);
+                self.dropped += (len - kept) as u64;
+                self.stats.record_drop(len - kept);
+                self.report_loss((len - kept) as u64);
+
+                kept
+            }
+        }
+    }
+
+    /// Updates the bookkeeping after `len` elements have been written.
+    fn finish_write(&mut self, len: usize) {
+        // Without readers, more than `available` elements may be written
+        self.available = self.available.saturating_sub(len);
+        self.generation += Wrapping(1);
+        self.written += len as u64;
+        self.stats.record_write(len);
+        self.run_evictions();
+        self.check_lag(len);
+    }
+
+    /// Writes a single element and calls `on_evict` once it is no longer
+    /// stored because newer elements took its place.
+    ///
+    /// If the element isn't written right away, because of the `DropPolicy`
+    /// or because writing is paused, `on_evict` is called immediately.
+    pub fn single_write_with_eviction(&mut self, element: T, on_evict: EvictionCallback) {
+        match self.single_write_handle(element) {
+            Some(handle) => self.evictions.get_mut().push_back((handle.sequence, on_evict)),
+            None => on_evict(),
+        }
+    }
+
+    /// Calls the eviction callbacks of all elements which are no longer
+    /// stored.
+    fn run_evictions(&mut self) {
+        let oldest = self.oldest();
+        let evictions = self.evictions.get_mut();
+        while let Some(&(sequence, _)) = evictions.front() {
+            if sequence >= oldest {
+                break;
+            }
+            let (_, on_evict) = evictions.pop_front().unwrap();
+            on_evict();
+        }
+    }
+
+    /// Sets a function to be called with the number of elements whenever a
+    /// write drops elements or a cursor read skips them.
+    pub fn set_loss_hook(&mut self, hook: LossHook) {
+        self.loss_hook = Some(hook);
+    }
+
+    /// Sets a function to be called with the id and lag of every reader
+    /// whose lag exceeds `threshold` because of a write.
+    pub fn set_lag_alarm(&mut self, threshold: usize, alarm: LagAlarm) {
+        self.lag_alarm = Some((threshold, NoSharedAccess::new(alarm)));
+    }
+
+    /// Calls the lag alarm for the readers which were at or below the
+    /// threshold before the last write of `len` elements.
+    fn check_lag(&mut self, len: usize) {
+        if self.lag_alarm.is_none() {
+            return;
+        }
+        // Dropped readers must not trigger the alarm.
+        self.maintain();
+
+        let (last, gen) = (self.last_index, self.generation.0);
+        let (threshold, alarm) = self.lag_alarm.as_mut().unwrap();
+        for (id, reader) in self.meta.readers.iter_mut().enumerate() {
+            let reader = reader.get_mut();
+            if !reader.active() {
+                continue;
+            }
+            let lag = reader.pending(last, gen);
+            if lag > *threshold && lag.saturating_sub(len) <= *threshold {
+                (alarm.get_mut())(id, lag);
+            }
+        }
+    }
+
+    fn report_loss(&self, lost: u64) {
+        if let (Some(hook), true) = (&self.loss_hook, lost > 0) {
+            hook(lost);
+        }
+    }
+
+    /// Sets a function to be called whenever an empty write is performed.
+    pub fn set_empty_write_hook(&mut self, hook: Box<dyn FnMut() + Send>) {
+        self.empty_write_hook = Some(NoSharedAccess::new(hook));
+    }
+
+    /// Pauses or resumes writing.
+    ///
+    /// While paused, written elements are queued instead. Resuming writes
+    /// all of them at once.
+    pub fn set_paused(&mut self, paused: bool) {
+        match (paused, self.paused.take()) {
+            (true, queued) => self.paused = Some(queued.unwrap_or_default()),
+            (false, Some(queued)) => self.write_unfiltered(queued.into_iter()),
+            (false, None) => {}
         }
     }
 
+    /// Checks if writing is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Adds a function which gets called with every element written.
+    pub fn add_tap(&mut self, tap: Tap<T>) {
+        self.taps.push(NoSharedAccess::new(tap));
+    }
+
+    /// Removes all functions added with `add_tap`.
+    pub fn clear_taps(&mut self) {
+        self.taps.clear();
+    }
+
+    /// Sets a function which every element written is passed through, and
+    /// which can replace it or drop it by returning `None`.
+    pub fn set_ingest_filter(&mut self, filter: IngestFilter<T>) {
+        self.ingest_filter = Some(NoSharedAccess::new(filter));
+    }
+
+    /// Sets a function which receives every element that gets overwritten.
+    pub fn set_recycle_sink(&mut self, sink: RecycleSink<T>) {
+        self.recycle_sink = Some(NoSharedAccess::new(sink));
+    }
+
+    /// Pushes clones of the last `capacity()` elements of `data` to the
+    /// buffer, skipping the ones before.
+    pub fn append_clamped(&mut self, data: &[T])
+    where
+        T: Clone,
+    {
+        let skip = data.len().saturating_sub(self.capacity());
+
+        self.iter_write(data[skip..].iter().cloned());
+    }
+
+    /// Drains the last `capacity()` elements of `data` into the buffer,
+    /// dropping the ones before, and returns how many were dropped, including
+    /// the ones the `DropPolicy` dropped.
+    pub fn write_lossy(&mut self, data: &mut Vec<T>) -> usize {
+        let (skip, dropped) = (data.len().saturating_sub(self.capacity()), self.dropped);
+        self.iter_write(data.drain(..).skip(skip));
+
+        skip + (self.dropped - dropped) as usize
+    }
+
+    /// Returns the number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.last_index.size
+    }
+
     /// Removes all elements from a `Vec` and pushes them to the ring buffer.
     pub fn drain_vec_write(&mut self, data: &mut Vec<T>) {
         self.iter_write(data.drain(..));
@@ -408,6 +1078,20 @@ impl<T: 'static> RingBuffer<T> {
         self.ensure_additional_slow(num);
     }
 
+    /// Returns how many of `num` elements can be inserted without growing or
+    /// overwriting unread elements.
+    fn reserve_up_to(&mut self, num: usize) -> usize {
+        if self.available < num {
+            self.maintain();
+            self.available = match self.meta.nearest_index(self.last_index, self.generation.0) {
+                None => return num,
+                Some(reader) => reader.distance_from(self.last_index, self.generation.0),
+            };
+        }
+
+        self.available.min(num)
+    }
+
     #[inline(never)]
     fn ensure_additional_slow(&mut self, num: usize) {
         self.maintain();
@@ -430,12 +1114,17 @@ impl<T: 'static> RingBuffer<T> {
             }
         };
         let grow_by = num - left;
-        let min_target_size = self.last_index.size + grow_by;
+        let min_target_size = self
+            .last_index
+            .size
+            .checked_add(grow_by)
+            .expect("capacity overflow");
 
         // Make sure size' = 2^n * size
-        let mut size = 2 * self.last_index.size;
+        let double = |size: usize| size.checked_mul(2).expect("capacity overflow");
+        let mut size = double(self.last_index.size);
         while size < min_target_size {
-            size *= 2;
+            size = double(size);
         }
 
         // Calculate adjusted growth
@@ -452,12 +1141,39 @@ impl<T: 'static> RingBuffer<T> {
         self.available = grow_by + left
     }
 
-    fn maintain(&mut self) {
+    /// Unregisters all readers which have been dropped.
+    pub fn maintain(&mut self) {
+        if !self.atomic_readers.is_empty() {
+            self.sync_atomic_readers();
+        }
         while let Ok(id) = self.free_rx.get_mut().try_recv() {
             self.meta.remove(id);
         }
     }
 
+    /// Moves the readers backing the `AtomicReaderId`s to the positions of
+    /// those, and drops the ones whose `AtomicReaderId` has been dropped.
+    fn sync_atomic_readers(&mut self) {
+        let meta = &mut self.meta;
+        self.atomic_readers.retain(|&(ref position, id)| {
+            let alive = Arc::strong_count(position) > 1;
+            if !alive {
+                meta.remove(id);
+            }
+
+            alive
+        });
+
+        let (last, gen, oldest) = (self.last_index, self.generation.0, self.oldest());
+        for &(ref position, id) in &self.atomic_readers {
+            let start = position.load(atomic::Ordering::Acquire).max(oldest);
+            let pending = self.written.saturating_sub(start) as usize;
+            let reader = self.meta.reader_exclusive(id);
+            let behind = reader.pending(last, gen);
+            reader.advance(behind.saturating_sub(pending), last, gen);
+        }
+    }
+
     /// Write a single data point into the ring buffer.
     pub fn single_write(&mut self, element: T) {
         use std::iter::once;
@@ -465,54 +1181,528 @@ impl<T: 'static> RingBuffer<T> {
         self.iter_write(once(element));
     }
 
+    /// Writes a single element, unless an element with the same key is
+    /// stored which no reader has read yet, in which case that element is
+    /// replaced. Returns `true` if an element was replaced.
+    pub fn write_coalesced<K, F>(&mut self, element: T, mut key: F) -> bool
+    where
+        K: PartialEq,
+        F: FnMut(&T) -> K,
+    {
+        let element = match &mut self.ingest_filter {
+            Some(filter) => match (filter.get_mut())(element) {
+                Some(element) => element,
+                None => return false,
+            },
+            None => element,
+        };
+        let wanted = key(&element);
+
+        if let Some(paused) = &mut self.paused {
+            // Queued elements haven't been read by anyone.
+            if let Some(queued) = paused.iter_mut().rev().find(|queued| key(queued) == wanted) {
+                *queued = element;
+                return true;
+            }
+        } else {
+            self.maintain();
+            let (last, gen) = (self.last_index, self.generation.0);
+            let unread = self
+                .meta
+                .farthest_index(last, gen)
+                .map_or(0, |reader| reader.pending(last, gen));
+            let found = (0..unread)
+                .find(|&lag| key(unsafe { self.data.get(last - lag) }) == wanted);
+            if let Some(lag) = found {
+                for tap in &mut self.taps {
+                    (tap.get_mut())(&element);
+                }
+                let old = mem::replace(unsafe { self.data.get_mut(last - lag) }, element);
+                if let Some(sink) = &mut self.recycle_sink {
+                    (sink.get_mut())(old);
+                }
+                return true;
+            }
+        }
+        self.write_unfiltered(iter::once(element));
+
+        false
+    }
+
+    /// Writes a single element, unless that would grow the buffer or
+    /// overwrite an element some reader hasn't read, in which case it is
+    /// returned.
+    pub fn try_single_write(&mut self, element: T) -> Result<(), T> {
+        if self.paused.is_none() && self.reserve_up_to(1) == 0 {
+            return Err(element);
+        }
+        self.single_write(element);
+
+        Ok(())
+    }
+
     /// Create a new reader id for this ring buffer.
     pub fn new_reader_id(&mut self) -> ReaderId<T> {
+        self.new_reader_id_with(None)
+    }
+
+    /// Create a new reader id for this ring buffer, labeled with `label`.
+    pub fn new_reader_id_labeled(&mut self, label: &'static str) -> ReaderId<T> {
+        self.new_reader_id_with(Some(label))
+    }
+
+    /// Create a new reader id for this ring buffer, which skips elements
+    /// older than the `max_age` most recent ones.
+    ///
+    /// Panics if `max_age` is zero.
+    pub fn new_reader_id_max_age(&mut self, max_age: usize) -> ReaderId<T> {
+        assert!(max_age > 0, "the maximum age of a reader must not be zero");
+        let reader_id = self.new_reader_id();
+        self.meta.reader_exclusive(reader_id.id).max_age = max_age;
+
+        reader_id
+    }
+
+    /// Sets the maximum age of the readers created from now on, except for
+    /// the ones created with `new_reader_id_max_age`.
+    ///
+    /// Panics if `max_age` is zero.
+    pub fn set_reader_max_age(&mut self, max_age: usize) {
+        assert!(max_age > 0, "the maximum age of a reader must not be zero");
+        self.reader_max_age = max_age;
+    }
+
+    fn new_reader_id_with(&mut self, label: Option<&'static str>) -> ReaderId<T> {
         self.maintain();
-        let last_index = self.last_index.index;
-        let generation = self.generation.0;
-        let id = self.meta.alloc(last_index, generation);
+        let id = self.meta.alloc(self.reader_at_end());
 
         ReaderId {
             id,
+            label,
             marker: PhantomData,
             reference: self.instance_id.reference(),
             drop_notifier: NoSharedAccess::new(self.free_tx.get_mut().clone()),
         }
     }
 
-    /// Duplicate a reader id from an existing one.
-    pub fn duplicate_reader_id(&mut self, reader_id: &ReaderId<T>) -> ReaderId<T> {
+    /// Returns the state of a new reader, which has nothing to read yet.
+    fn reader_at_end(&self) -> Reader {
+        Reader {
+            generation: self.generation.0,
+            last_index: self.last_index.index,
+            read_at: self.written,
+            dropped_at: self.dropped,
+            max_age: self.reader_max_age,
+        }
+    }
+
+    /// Create a new atomic reader id for this ring buffer.
+    pub fn new_atomic_reader_id(&mut self) -> AtomicReaderId<T> {
         self.maintain();
-        let reader: &Reader = self.meta.reader_const(reader_id).unwrap_or_else(|| {
-            panic!(
-                "ReaderId not registered: {}\n\
+        // Its position decides what is read, not the maximum age.
+        let id = self.meta.alloc(Reader {
+            max_age: usize::MAX,
+            ..self.reader_at_end()
+        });
+        let position = Arc::new(AtomicU64::new(self.written));
+        self.atomic_readers.push((position.clone(), id));
+
+        AtomicReaderId {
+            position,
+            marker: PhantomData,
+            reference: self.instance_id.reference(),
+        }
+    }
+
+    /// Reads all elements written since the last read with `reader_id`.
+    pub fn read_atomic(&self, reader_id: &AtomicReaderId<T>) -> StorageIterator<'_, T> {
+        self.instance_id.assert_eq(&reader_id.reference);
+        // Concurrent reads get disjoint ranges, since writing needs `&mut self`.
+        let start = reader_id
+            .position
+            .swap(self.written, atomic::Ordering::AcqRel)
+            .max(self.oldest())
+            .min(self.written);
+
+        let iter = self.iter_newest((self.written - start) as usize);
+        self.stats.record_read(iter.len(), 0);
+
+        iter
+    }
+
+    /// Reads up to `max` of the elements after the position of `reader_id`.
+    ///
+    /// The elements are claimed with a compare-and-swap of the position, so
+    /// concurrent reads get disjoint ranges, and none reads past another.
+    pub fn read_atomic_batch(
+        &self,
+        reader_id: &AtomicReaderId<T>,
+        max: usize,
+    ) -> StorageIterator<'_, T> {
+        self.instance_id.assert_eq(&reader_id.reference);
+        let oldest = self.oldest();
+        let mut position = reader_id.position.load(atomic::Ordering::Acquire);
+        let (start, num) = loop {
+            let start = position.max(oldest).min(self.written);
+            let num = ((self.written - start) as usize).min(max);
+            match reader_id.position.compare_exchange_weak(
+                position,
+                start + num as u64,
+                atomic::Ordering::AcqRel,
+                atomic::Ordering::Acquire,
+            ) {
+                Ok(_) => break (start, num),
+                Err(current) => position = current,
+            }
+        };
+
+        let iter = self.iter_at((start - oldest) as usize, num);
+        self.stats.record_read(iter.len(), 0);
+
+        iter
+    }
+
+    /// Returns how many elements `reader_id` has not read yet.
+    pub fn atomic_reader_lag(&self, reader_id: &AtomicReaderId<T>) -> usize {
+        self.instance_id.assert_eq(&reader_id.reference);
+        let start = reader_id.position.load(atomic::Ordering::Acquire);
+
+        (self.written - start.max(self.oldest()).min(self.written)) as usize
+    }
+
+    /// Create a new reader id which has the elements from `sequence` on
+    /// pending, or all stored elements if `sequence` is no longer stored.
+    pub fn new_reader_id_at(&mut self, sequence: u64) -> ReaderId<T> {
+        let sequence = sequence.max(self.oldest()).min(self.written);
+        let pending = (self.written - sequence) as usize;
+
+        let reader_id = self.new_reader_id();
+        if pending > 0 {
+            let (last, gen) = (self.last_index, self.generation.0);
+            let reader = self.meta.reader_exclusive(reader_id.id);
+            // Like in `readers_to_oldest`, a whole lap pending ends up at
+            // `last_index` with an outdated generation.
+            reader.last_index = last - pending;
+            reader.generation = gen.wrapping_sub(1);
+            self.available = self.available.min(last.size - pending);
+        }
+
+        reader_id
+    }
+
+    /// Duplicate a reader id from an existing one.
+    pub fn duplicate_reader_id(&mut self, reader_id: &ReaderId<T>) -> ReaderId<T> {
+        self.maintain();
+        let reader: &Reader = self.meta.reader_const(reader_id).unwrap_or_else(|| {
+            panic!(
+                "ReaderId not registered: {}\n\
                  This usually means that this ReaderId \
                  was created by a different storage",
                 reader_id.id
             )
         });
         
-        let index = reader.last_index;
-        let gen = reader.generation;
-        let id = self.meta.alloc(index, gen);
+        let reader = *reader;
+        let id = self.meta.alloc(reader);
 
         ReaderId {
             id,
+            label: reader_id.label,
             marker: PhantomData,
             reference: self.instance_id.reference(),
             drop_notifier: NoSharedAccess::new(self.free_tx.get_mut().clone())
         }
     }
 
+    /// Returns how many elements `reader_id` has not read yet, not counting
+    /// the ones it skips because of its maximum age.
+    pub fn reader_lag(&self, reader_id: &ReaderId<T>) -> usize {
+        let reader = self.reader_state(reader_id);
+
+        reader.pending(self.last_index, self.generation.0).min(reader.max_age)
+    }
+
+    /// Returns how many elements the reader furthest behind has not read yet,
+    /// or zero if there is no reader.
+    pub fn slowest_reader_lag(&mut self) -> usize {
+        self.maintain();
+        let (last, gen) = (self.last_index, self.generation.0);
+
+        self.meta
+            .nearest_index(last, gen)
+            .map_or(0, |reader| reader.pending(last, gen))
+    }
+
+    /// Returns the id and lag of every reader.
+    pub fn reader_positions(&mut self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.maintain();
+        let (last, gen) = (self.last_index, self.generation.0);
+
+        self.meta
+            .readers
+            .iter_mut()
+            .map(|reader| reader.get_mut())
+            .enumerate()
+            .filter(|(_, reader)| reader.active())
+            .map(move |(id, reader)| (id, reader.pending(last, gen)))
+    }
+
+    /// Returns a snapshot of the positions of all readers and the stored
+    /// elements.
+    pub fn debug_dump(&mut self) -> ChannelDump<'_, T> {
+        let written = self.written;
+        let readers = self
+            .reader_positions()
+            .map(|(id, lag)| ReaderDump {
+                id,
+                position: written - lag as u64,
+                lag,
+            })
+            .collect();
+
+        ChannelDump {
+            next_sequence: written,
+            oldest: self.oldest(),
+            capacity: self.capacity(),
+            readers,
+            events: self.iter_all(),
+        }
+    }
+
+    /// Advances all `readers` past the elements they have not read yet, and
+    /// returns all elements at least one of them had not read yet.
+    pub fn read_coalesced(&self, readers: &mut [ReaderId<T>]) -> StorageIterator<'_, T> {
+        let mut num = 0;
+        for reader_id in readers {
+            num = num.max(self.ack(reader_id, usize::MAX));
+        }
+        self.stats.record_read(num, 0);
+
+        self.iter_newest(num)
+    }
+
+    /// Returns the number of elements dropped because of the `DropPolicy`
+    /// since `reader_id` was registered.
+    pub fn reader_total_lost(&self, reader_id: &ReaderId<T>) -> u64 {
+        self.dropped - self.reader_state(reader_id).dropped_at
+    }
+
+    /// Returns the number of elements written since `reader_id` last read.
+    pub fn reader_staleness(&self, reader_id: &ReaderId<T>) -> u64 {
+        self.written - self.reader_state(reader_id).read_at
+    }
+
+    /// Returns a reusable view of the elements `reader_id` has not read yet.
+    pub fn snapshot(&self, reader_id: &ReaderId<T>) -> SnapshotReader<'_, T> {
+        SnapshotReader {
+            events: self.peek(reader_id),
+        }
+    }
+
+    /// Returns the elements `reader_id` has not read yet, without advancing
+    /// it.
+    pub fn peek(&self, reader_id: &ReaderId<T>) -> StorageIterator<'_, T> {
+        self.iter_newest(self.reader_lag(reader_id))
+    }
+
+    /// Returns the elements `reader_id` has not read yet, without advancing
+    /// it, along with a token to advance it past them with `commit`.
+    pub fn read_uncommitted(
+        &self,
+        reader_id: &ReaderId<T>,
+    ) -> (StorageIterator<'_, T>, CommitToken) {
+        let len = self.reader_lag(reader_id);
+        let token = CommitToken {
+            reader: reader_id.id,
+            start: self.written - len as u64,
+            len,
+        };
+
+        (self.iter_newest(len), token)
+    }
+
+    /// Advances `reader_id` past the elements of `token`, unless it has been
+    /// moved since `token` was created.
+    pub fn commit(
+        &self,
+        reader_id: &mut ReaderId<T>,
+        token: CommitToken,
+    ) -> Result<usize, StaleCommit> {
+        let start = self.written - self.reader_lag(reader_id) as u64;
+        if token.reader != reader_id.id || token.start != start {
+            return Err(StaleCommit);
+        }
+
+        Ok(self.ack(reader_id, token.len))
+    }
+
+    /// Returns up to `max` of the elements `reader_id` has not read yet,
+    /// advancing it past them, along with how many elements remain.
+    pub fn read_batch(
+        &self,
+        reader_id: &mut ReaderId<T>,
+        max: usize,
+    ) -> (StorageIterator<'_, T>, usize) {
+        let pending = self.reader_lag(reader_id);
+        let iter = self.iter_window(self.data.num_initialized() - pending, max);
+        self.ack(reader_id, iter.len());
+        self.stats.record_read(iter.len(), 0);
+
+        let remaining = pending - iter.len();
+        (iter, remaining)
+    }
+
+    /// Returns the current position of `reader_id`.
+    pub fn checkpoint(&self, reader_id: &ReaderId<T>) -> ReaderCheckpoint {
+        ReaderCheckpoint {
+            reader: reader_id.id,
+            sequence: self.written - self.reader_lag(reader_id) as u64,
+        }
+    }
+
+    /// Moves `reader_id` back (or forward) to `checkpoint`, returning how
+    /// many elements it has pending then.
+    pub fn restore(
+        &mut self,
+        reader_id: &mut ReaderId<T>,
+        checkpoint: ReaderCheckpoint,
+    ) -> Result<usize, CheckpointExpired> {
+        let sequence = checkpoint.sequence;
+        let valid = checkpoint.reader == reader_id.id && sequence <= self.written;
+        if !valid || sequence < self.oldest() {
+            return Err(CheckpointExpired);
+        }
+
+        let pending = (self.written - sequence) as usize;
+        let (last, gen) = (self.last_index, self.generation.0);
+        let reader = self.reader_state_mut(reader_id);
+        reader.last_index = last - pending;
+        // Like in `new_reader_id_at`, a whole lap pending ends up at
+        // `last_index` with an outdated generation.
+        reader.generation = match pending {
+            0 => gen,
+            _ => gen.wrapping_sub(1),
+        };
+        self.available = self.available.min(last.size - pending);
+
+        Ok(pending)
+    }
+
+    /// Advances `reader_id` by `num` elements, or less if it doesn't have as
+    /// many pending. Returns by how many elements it was advanced.
+    pub fn ack(&self, reader_id: &mut ReaderId<T>, num: usize) -> usize {
+        let (last, gen) = (self.last_index, self.generation.0);
+        let reader = self.reader_state_mut(reader_id);
+        reader.advance(reader.stale(last, gen), last, gen);
+        let num = num.min(reader.pending(last, gen));
+        reader.advance(num, last, gen);
+        reader.read_at = self.written;
+
+        num
+    }
+
+    /// Calls `f` on every element `reader_id` has not read yet, then advances
+    /// it past them. Returns the number of elements.
+    pub fn read_transform<F>(&mut self, reader_id: &mut ReaderId<T>, mut f: F) -> usize
+    where
+        F: FnMut(&mut T),
+    {
+        let num = self.reader_lag(reader_id);
+        let start = self.data.num_initialized() - num;
+        for n in start..start + num {
+            let index = self.physical(n);
+            f(unsafe { self.data.get_mut(index) });
+        }
+
+        self.ack(reader_id, num)
+    }
+
+    /// Advances `reader_id` past all elements it has not read yet, returning
+    /// an iterator which takes them out of the buffer one by one, leaving
+    /// `T::default()` in their place.
+    pub fn drain_read<'a>(
+        &'a mut self,
+        reader_id: &mut ReaderId<T>,
+    ) -> impl Iterator<Item = T> + 'a
+    where
+        T: Default,
+    {
+        let num = self.reader_lag(reader_id);
+        self.ack(reader_id, num);
+        self.stats.record_read(num, 0);
+
+        let (last, len) = (self.last_index, self.data.num_initialized());
+        let data = &mut self.data;
+
+        (len - num..len).map(move |n| {
+            let index = last - (len - 1 - n);
+
+            mem::take(unsafe { data.get_mut(index) })
+        })
+    }
+
+    fn reader_state_mut(&self, reader_id: &mut ReaderId<T>) -> &mut Reader {
+        self.instance_id.assert_eq(&reader_id.reference);
+        let id = reader_id.id;
+
+        self.meta.reader(reader_id).unwrap_or_else(|| {
+            panic!(
+                "ReaderId not registered: {}\n\
+                 This usually means that this ReaderId \
+                 was created by a different storage",
+                id
+            )
+        })
+    }
+
+    fn reader_state(&self, reader_id: &ReaderId<T>) -> &Reader {
+        self.instance_id.assert_eq(&reader_id.reference);
+
+        self.meta.reader_const(reader_id).unwrap_or_else(|| {
+            panic!(
+                "ReaderId not registered: {}\n\
+                 This usually means that this ReaderId \
+                 was created by a different storage",
+                reader_id.id
+            )
+        })
+    }
+
+    /// Create a new reader id for this ring buffer, after checking that
+    /// `reader_id` belongs to it.
+    pub fn clone_reader_at_head(&mut self, reader_id: &ReaderId<T>) -> ReaderId<T> {
+        self.reader_state(reader_id);
+
+        self.new_reader_id()
+    }
+
+    /// Unregisters `reader_id` immediately, after checking that it belongs to
+    /// this ring buffer.
+    pub fn purge_reader_id(&mut self, reader_id: ReaderId<T>) {
+        self.reader_state(&reader_id);
+        drop(reader_id);
+
+        self.maintain();
+    }
+
+    /// Returns `reader_id` if it belongs to this ring buffer, a new reader id
+    /// otherwise.
+    pub fn adopt_reader_id(&mut self, reader_id: ReaderId<T>) -> ReaderId<T> {
+        match self.instance_id == reader_id.reference {
+            true => reader_id,
+            false => self.new_reader_id_with(reader_id.label),
+        }
+    }
+
     /// Read data from the ring buffer, starting where the last read ended, and
     /// up to where the last element was written.
-    pub fn read(&self, reader_id: &mut ReaderId<T>) -> StorageIterator<T> {
+    pub fn read(&self, reader_id: &mut ReaderId<T>) -> StorageIterator<'_, T> {
         // Check if `reader_id` was actually created for this buffer.
         // This is very important as `reader_id` is a token allowing memory access,
         // and without this check a race could be caused by duplicate IDs.
         self.instance_id.assert_eq(&reader_id.reference);
 
-        let (last_read_index, gen) = {
+        let (last_read_index, stale) = {
             let reader = self.meta.reader(reader_id).unwrap_or_else(|| {
                 panic!(
                     "ReaderId not registered: {}\n\
@@ -521,28 +1711,459 @@ impl<T: 'static> RingBuffer<T> {
                     reader_id.id
                 )
             });
+            reader.read_at = self.written;
+            if reader.generation == self.generation.0 {
+                // Fast path for readers polling without anything new; the
+                // reader is already up to date.
+                self.stats.record_read(0, 0);
+
+                return self.iter_at(0, 0);
+            }
+
+            let stale = reader.stale(self.last_index, self.generation.0);
+            let pending = reader.pending(self.last_index, self.generation.0);
             let old = reader.last_index;
             reader.last_index = self.last_index.index;
-            let old_gen = reader.generation;
             reader.generation = self.generation.0;
+            if stale == pending {
+                // Starting after all stale elements would wrap around to the
+                // oldest slot and walk a whole lap.
+                self.stats.record_read(0, 0);
+
+                return self.iter_at(0, 0);
+            }
 
-            (old, old_gen)
+            (old, stale)
         };
         let mut index = CircularIndex::new(last_read_index, self.last_index.size);
-        index += 1;
-        if gen == self.generation.0 {
-            // It is empty
-            index = CircularIndex::magic(index.size);
-        }
+        index += 1 + stale;
 
         let iter = StorageIterator {
             data: &self.data,
             end: self.last_index.index,
             index,
         };
+        self.stats.record_read(iter.len(), 0);
 
         iter
     }
+
+    /// Returns the total number of elements written so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Create a new cursor pointing at the current end of the ring buffer.
+    pub fn new_cursor(&self) -> Cursor {
+        Cursor {
+            written: self.written,
+        }
+    }
+
+    /// Read data from the ring buffer, starting at the position of `cursor`,
+    /// and up to where the last element was written.
+    ///
+    /// Events which were overwritten since the last read with `cursor` are
+    /// skipped.
+    pub fn read_cursor(&self, cursor: &mut Cursor) -> StorageIterator<'_, T> {
+        // Clamping also handles cursors of other buffers
+        let start = cursor.written.max(self.oldest()).min(self.written);
+        let lost = start.saturating_sub(cursor.written);
+        cursor.written = self.written;
+
+        let iter = self.iter_newest((self.written - start) as usize);
+        self.stats.record_read(iter.len(), lost);
+        self.report_loss(lost);
+
+        iter
+    }
+
+    /// Returns the statistics collected so far.
+    pub fn stats(&self) -> Stats {
+        self.stats.get()
+    }
+
+    /// Resets all statistics to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+    }
+
+    /// Returns how many elements `cursor` missed because they have been
+    /// overwritten.
+    pub fn cursor_lost(&self, cursor: &Cursor) -> u64 {
+        self.oldest().saturating_sub(cursor.written)
+    }
+
+    /// Returns the sequence number of the oldest stored element.
+    fn oldest(&self) -> u64 {
+        self.written - self.data.num_initialized() as u64
+    }
+
+    /// Iterates over all elements still stored, from the oldest to the newest.
+    pub fn iter_all(&self) -> StorageIterator<'_, T> {
+        self.iter_window(0, usize::MAX)
+    }
+
+    /// Writes a single element, returning a handle to it unless it was
+    /// dropped because of the `DropPolicy`.
+    pub fn single_write_handle(&mut self, element: T) -> Option<EventHandle> {
+        let sequence = self.written;
+        self.single_write(element);
+
+        match self.written > sequence {
+            true => Some(EventHandle { sequence }),
+            false => None,
+        }
+    }
+
+    /// Writes the elements of `iter`, returning the range of sequence numbers
+    /// they got.
+    pub fn iter_write_returning<I>(&mut self, iter: I) -> EventRange
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let start = self.written;
+        self.iter_write(iter);
+
+        EventRange {
+            start,
+            end: self.written,
+        }
+    }
+
+    /// Iterates over the elements of `range` which are still stored.
+    pub fn iter_range(&self, range: EventRange) -> StorageIterator<'_, T> {
+        let oldest = self.oldest();
+        let start = range.start.max(oldest).min(self.written);
+        let end = range.end.max(start).min(self.written);
+
+        self.iter_window((start - oldest) as usize, (end - start) as usize)
+    }
+
+    /// Returns the element `handle` refers to, if it is still stored.
+    pub fn get_by_handle(&self, handle: EventHandle) -> Option<&T> {
+        let oldest = self.oldest();
+        match handle.sequence >= oldest && handle.sequence < self.written {
+            true => {
+                let index = self.physical((handle.sequence - oldest) as usize);
+
+                Some(unsafe { self.data.get(index) })
+            }
+            false => None,
+        }
+    }
+
+    /// Returns the element written `lag` elements before the newest one, if
+    /// it is still stored.
+    pub fn at_lag(&self, lag: usize) -> Option<&T> {
+        match lag < self.data.num_initialized() {
+            true => Some(unsafe { self.data.get(self.last_index - lag) }),
+            false => None,
+        }
+    }
+
+    /// Returns the `n`th oldest element still stored.
+    pub fn get(&self, n: usize) -> Option<&T> {
+        match n < self.data.num_initialized() {
+            true => Some(unsafe { self.data.get(self.physical(n)) }),
+            false => None,
+        }
+    }
+
+    /// Returns the `n`th oldest element still stored, mutably.
+    pub fn get_mut(&mut self, n: usize) -> Option<&mut T> {
+        match n < self.data.num_initialized() {
+            true => Some(unsafe { self.data.get_mut(self.physical(n)) }),
+            false => None,
+        }
+    }
+
+    /// Returns the elements still stored, from the oldest to the newest, as
+    /// two slices.
+    ///
+    /// The second slice is only non-empty if the elements wrap around the
+    /// end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.iter_all().as_slices()
+    }
+
+    /// Returns a pointer to the first of the `capacity()` slots of the
+    /// buffer, in physical order.
+    pub fn as_ptr(&self) -> *const T {
+        self.data.data.as_ptr()
+    }
+
+    /// Rotates the buffer so the oldest stored element is at the start of
+    /// it, keeping the positions of all readers.
+    pub fn compact(&mut self) {
+        let size = self.last_index.size;
+        // The uninitialized slots are the ones right before the oldest element.
+        let oldest = CircularIndex::new(self.last_index + 1, size) + self.data.uninitialized;
+        if oldest == 0 {
+            return;
+        }
+
+        unsafe {
+            self.data.rotate_left(oldest);
+        }
+        self.last_index -= oldest;
+        for reader in self.meta.readers.iter_mut().map(|r| r.get_mut()) {
+            if reader.active() {
+                reader.last_index = CircularIndex::new(reader.last_index, size) - oldest;
+            }
+        }
+    }
+
+    /// Drops all elements with a sequence number below `sequence`, moving
+    /// the readers past them. Returns how many elements were dropped.
+    pub fn expire_before(&mut self, sequence: u64) -> usize {
+        self.maintain();
+        let (oldest, written) = (self.oldest(), self.written);
+        let sequence = sequence.min(written);
+        if sequence <= oldest {
+            return 0;
+        }
+
+        let (last, gen) = (self.last_index, self.generation.0);
+        for reader in self.meta.readers.iter_mut().map(|r| r.get_mut()) {
+            if reader.active() {
+                let first = written - reader.pending(last, gen) as u64;
+                reader.advance(sequence.saturating_sub(first) as usize, last, gen);
+            }
+        }
+
+        let num = (sequence - oldest) as usize;
+        unsafe {
+            self.data.drop_oldest(self.last_index + 1, num);
+        }
+        self.stats.record_expiry(num);
+        self.run_evictions();
+
+        num
+    }
+
+    /// Drops all elements which every reader has read, returning how many
+    /// those were.
+    pub fn clear_consumed(&mut self) -> usize {
+        let lag = self.slowest_reader_lag();
+        let num = self.data.num_initialized() - lag;
+        unsafe {
+            self.data.drop_oldest(self.last_index + 1, num);
+        }
+        self.run_evictions();
+
+        num
+    }
+
+    /// Reduces the capacity to `size`, keeping the newest elements, but never
+    /// below what the readers have pending.
+    pub fn shrink_to(&mut self, size: usize) {
+        let lag = self.slowest_reader_lag();
+        let size = size.max(lag).max(2);
+        if size >= self.last_index.size {
+            return;
+        }
+
+        self.compact();
+        let (last, gen) = (self.last_index, self.generation.0);
+        let len = self.data.num_initialized();
+        let num = len.saturating_sub(size);
+        unsafe {
+            self.data.shrink(num, size);
+        }
+
+        // The elements are at the start now, followed by the free slots.
+        self.last_index = CircularIndex::new(CircularIndex::at_end(size) + (len - num), size);
+        let new_last = self.last_index;
+        for reader in self.meta.readers.iter_mut().map(|r| r.get_mut()) {
+            if reader.active() {
+                let pending = reader.pending(last, gen);
+                reader.last_index = new_last - pending;
+            }
+        }
+        self.available = size - lag;
+        self.run_evictions();
+    }
+
+    /// Grows the buffer so it can hold `size` elements. Does nothing if it
+    /// can hold that many already.
+    pub fn grow_to(&mut self, size: usize) {
+        if size <= self.last_index.size {
+            return;
+        }
+
+        // Without wrapping around, so nothing has to be moved if the last
+        // element is at the end.
+        let grow_by = size - self.last_index.size;
+        unsafe {
+            self.data.grow(self.last_index.index + 1, grow_by);
+        }
+        self.last_index.size = size;
+
+        self.meta
+            .shift(self.last_index.index, self.generation.0, grow_by);
+        self.available += grow_by;
+    }
+
+    /// Swaps the stored elements, including their sequence numbers, with the
+    /// ones of `other`. All readers of both buffers are moved to the end.
+    pub fn swap_data(&mut self, other: &mut RingBuffer<T>) {
+        mem::swap(&mut self.data, &mut other.data);
+        mem::swap(&mut self.last_index, &mut other.last_index);
+        mem::swap(&mut self.written, &mut other.written);
+        mem::swap(self.evictions.get_mut(), other.evictions.get_mut());
+
+        self.readers_to_end();
+        other.readers_to_end();
+    }
+
+    /// Moves all readers back to the oldest element, so they have every
+    /// stored element to read.
+    pub fn readers_to_oldest(&mut self) {
+        self.maintain();
+        let len = self.data.num_initialized();
+        if len == 0 {
+            return;
+        }
+
+        // If all slots are initialized, this is `last_index` itself, and the
+        // outdated generation marks a whole lap as pending.
+        let index = self.last_index - len;
+        let generation = self.generation.0.wrapping_sub(1);
+        for reader in self.meta.readers.iter_mut().map(|r| r.get_mut()) {
+            if reader.active() {
+                reader.last_index = index;
+                reader.generation = generation;
+            }
+        }
+        let oldest = self.oldest();
+        for (position, _) in &self.atomic_readers {
+            position.store(oldest, atomic::Ordering::Release);
+        }
+        // Only the uninitialized slots are free now.
+        self.available = self.last_index.size - len;
+    }
+
+    /// Moves all readers to the newest element, so they have nothing to read.
+    pub fn readers_to_end(&mut self) {
+        self.maintain();
+        let (last, gen) = (self.last_index, self.generation.0);
+        for reader in self.meta.readers.iter_mut().map(|r| r.get_mut()) {
+            if reader.active() {
+                reader.last_index = last.index;
+                reader.generation = gen;
+            }
+        }
+        for (position, _) in &self.atomic_readers {
+            position.store(self.written, atomic::Ordering::Release);
+        }
+        self.available = last.size;
+    }
+
+    /// Moves all elements still stored out of the buffer, from the oldest to
+    /// the newest.
+    pub fn into_retained(mut self) -> Vec<T> {
+        let len = self.data.num_initialized();
+        let mut retained = Vec::with_capacity(len);
+        for n in 0..len {
+            retained.push(unsafe { ptr::read(self.data.get(self.physical(n))) });
+        }
+
+        // Everything has been moved out, so `Drop` must not touch it again.
+        unsafe {
+            self.data.forget();
+        }
+
+        retained
+    }
+
+    /// Iterates over up to `take` stored elements, skipping the `skip`
+    /// oldest ones.
+    pub fn iter_window(&self, skip: usize, take: usize) -> StorageIterator<'_, T> {
+        let len = self.data.num_initialized();
+        let skip = skip.min(len);
+
+        self.iter_at(skip, take.min(len - skip))
+    }
+
+    /// Binary searches the stored elements, from the oldest to the newest,
+    /// which must be sorted by the key `f` extracts.
+    ///
+    /// Like `slice::binary_search_by_key`, but the returned index counts the
+    /// stored elements from the oldest one instead of buffer positions.
+    pub fn binary_search_by_key<K, F>(&self, key: &K, mut f: F) -> Result<usize, usize>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let (mut low, mut high) = (0, self.data.num_initialized());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let element = unsafe { self.data.get(self.physical(mid)) };
+            match f(element).cmp(key) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(low)
+    }
+
+    /// Returns the number of stored elements, from the oldest one, for which
+    /// `pred` returns `true`, assuming it returns `false` for all elements
+    /// after them.
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (mut low, mut high) = (0, self.data.num_initialized());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match pred(unsafe { self.data.get(self.physical(mid)) }) {
+                true => low = mid + 1,
+                false => high = mid,
+            }
+        }
+
+        low
+    }
+
+    /// Iterates over the `num` most recently written elements.
+    fn iter_newest(&self, num: usize) -> StorageIterator<'_, T> {
+        self.iter_at(self.data.num_initialized() - num, num)
+    }
+
+    /// Returns the index into `data` of the `n`th oldest stored element.
+    fn physical(&self, n: usize) -> usize {
+        self.last_index - (self.data.num_initialized() - 1 - n)
+    }
+
+    /// Iterates over `num` elements, starting at the `start`th oldest stored
+    /// element.
+    fn iter_at(&self, start: usize, num: usize) -> StorageIterator<'_, T> {
+        let len = self.data.num_initialized();
+        debug_assert!(start + num <= len, "Bug in shrev");
+
+        let (index, end) = match num {
+            0 => (
+                CircularIndex::magic(self.last_index.size),
+                self.last_index.index,
+            ),
+            num => {
+                let first = CircularIndex::new(self.physical(start), self.last_index.size);
+
+                (first, first + (num - 1))
+            }
+        };
+
+        StorageIterator {
+            data: &self.data,
+            end,
+            index,
+        }
+    }
 }
 
 impl<T: Debug> Debug for RingBuffer<T> {
@@ -552,10 +2173,75 @@ impl<T: Debug> Debug for RingBuffer<T> {
             .field("instance_id", &self.instance_id)
             .field("data", &self.data)
             .field("last_index", &self.last_index)
+            .field("written", &self.written)
+            .field("policy", &self.policy)
+            .field("retained", &Retained(self))
             .finish()
     }
 }
 
+/// Formats the oldest and newest few elements of a ring buffer, so the
+/// output stays short for large buffers.
+struct Retained<'a, T>(&'a RingBuffer<T>);
+
+impl<T: Debug> Debug for Retained<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const SHOWN: usize = 4;
+
+        let (buffer, len) = (self.0, self.0.data.num_initialized());
+        // Same as `RingBuffer::physical`, which requires `T: 'static`
+        let nth = |n: usize| unsafe { buffer.data.get(buffer.last_index - (len - 1 - n)) };
+        let mut list = f.debug_list();
+        match len > 2 * SHOWN {
+            true => list
+                .entries((0..SHOWN).map(nth))
+                .entry(&format_args!("..{} more", len - 2 * SHOWN))
+                .entries((len - SHOWN..len).map(nth)),
+            false => list.entries((0..len).map(nth)),
+        };
+
+        list.finish()
+    }
+}
+
+/// Clones the stored elements and counters; readers, hooks and statistics
+/// are not cloned.
+impl<T: Clone + 'static> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut buffer = RingBuffer::with_policy(self.capacity(), self.policy);
+        buffer.clone_from(self);
+
+        buffer
+    }
+
+    /// Reuses the allocation of `self` if it is large enough. The readers of
+    /// `self` are moved to the end.
+    fn clone_from(&mut self, source: &Self) {
+        // None of the current elements are kept.
+        for (_, on_evict) in self.evictions.get_mut().drain(..) {
+            on_evict();
+        }
+
+        let size = source.capacity();
+        unsafe {
+            self.data.reset(self.last_index + 1, size);
+        }
+        self.last_index = CircularIndex::at_end(size);
+        for element in source.iter_all() {
+            let old = unsafe { self.data.put(self.last_index + 1, element.clone()) };
+            debug_assert!(old.is_none(), "Bug in shrev");
+            self.last_index += 1;
+        }
+
+        self.generation += Wrapping(1);
+        self.written = source.written;
+        self.dropped = source.dropped;
+        self.policy = source.policy;
+        self.reader_max_age = source.reader_max_age;
+        self.readers_to_end();
+    }
+}
+
 impl<T> Drop for RingBuffer<T> {
     fn drop(&mut self) {
         unsafe {
@@ -565,6 +2251,10 @@ impl<T> Drop for RingBuffer<T> {
 }
 
 /// Iterator over a slice of data in `RingBufferStorage`.
+///
+/// When obtained from a read, the reader has already been advanced past all
+/// elements, so stopping early, e.g. with `any`, skips the remaining ones for
+/// good. Use `peek` and `ack` to only consume some of them.
 #[derive(Debug)]
 pub struct StorageIterator<'a, T: 'a> {
     data: &'a Data<T>,
@@ -573,6 +2263,102 @@ pub struct StorageIterator<'a, T: 'a> {
     index: CircularIndex,
 }
 
+impl<'a, T> StorageIterator<'a, T> {
+    /// Returns the remaining elements as two slices.
+    ///
+    /// The second slice is only non-empty if the elements wrap around the end
+    /// of the ring buffer.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        if self.index.is_magic() {
+            return (&[], &[]);
+        }
+
+        let (start, end) = (self.index.index, self.end);
+        unsafe {
+            match start <= end {
+                true => (self.data.slice(start, end - start + 1), &[]),
+                false => (
+                    self.data.slice(start, self.index.size - start),
+                    self.data.slice(0, end + 1),
+                ),
+            }
+        }
+    }
+
+    /// Checks if there are no elements left.
+    ///
+    /// `len` is provided by `ExactSizeIterator`.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_magic()
+    }
+}
+
+// Not derived, since that would require `T: Clone`.
+impl<T> Clone for StorageIterator<'_, T> {
+    fn clone(&self) -> Self {
+        StorageIterator {
+            data: self.data,
+            end: self.end,
+            index: self.index,
+        }
+    }
+}
+
+/// A fixed view of the elements a reader had pending when it was created.
+///
+/// Unlike the iterator returned by a read, it can be iterated any number
+/// of times, and doesn't advance the reader.
+#[derive(Clone, Debug)]
+pub struct SnapshotReader<'a, T> {
+    events: StorageIterator<'a, T>,
+}
+
+impl<'a, T> SnapshotReader<'a, T> {
+    /// Iterates over the elements of the snapshot, from the oldest to the
+    /// newest.
+    pub fn iter(&self) -> StorageIterator<'a, T> {
+        self.events.clone()
+    }
+
+    /// Returns the number of elements in the snapshot.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if the snapshot doesn't contain any elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a, T> IntoIterator for &SnapshotReader<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StorageIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> StorageIterator<'_, T> {
+    /// Skips the next `num` elements.
+    fn skip_elements(&mut self, num: usize) {
+        match num < self.len() {
+            true => self.index += num,
+            false => self.index = CircularIndex::magic(self.index.size),
+        }
+    }
+
+    /// Returns the indices of the first and last remaining element within
+    /// the ring buffer, if any.
+    pub(crate) fn physical_range(&self) -> Option<(usize, usize)> {
+        match self.index.is_magic() {
+            true => None,
+            false => Some((self.index.index, self.end)),
+        }
+    }
+}
+
 impl<'a, T> Iterator for StorageIterator<'a, T> {
     type Item = &'a T;
 
@@ -588,6 +2374,43 @@ impl<'a, T> Iterator for StorageIterator<'a, T> {
 
         (len, Some(len))
     }
+
+    // `any` and `all` go through the elements as slices, which avoids the
+    // wrapping arithmetic of `next`.
+    fn any<F>(&mut self, f: F) -> bool
+    where
+        F: FnMut(&'a T) -> bool,
+    {
+        let (first, second) = self.as_slices();
+        let found = first.iter().chain(second).position(f);
+        self.skip_elements(found.map_or(usize::MAX, |n| n + 1));
+
+        found.is_some()
+    }
+
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(&'a T) -> bool,
+    {
+        !self.any(|e| !f(e))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StorageIterator<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.index.is_magic() {
+            return None;
+        }
+
+        let i = self.end;
+        if i == self.index.index {
+            self.index = CircularIndex::magic(self.index.size);
+        } else {
+            self.end = CircularIndex::new(i, self.index.size) - 1;
+        }
+
+        Some(unsafe { self.data.get(i) })
+    }
 }
 
 impl<'a, T> ExactSizeIterator for StorageIterator<'a, T> {
@@ -599,6 +2422,10 @@ impl<'a, T> ExactSizeIterator for StorageIterator<'a, T> {
     }
 }
 
+// Once exhausted, the index is magic, which `next` and `next_back` never
+// change again.
+impl<'a, T> FusedIterator for StorageIterator<'a, T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -608,9 +2435,63 @@ mod tests {
         pub id: u32,
     }
 
-    #[derive(Debug, Clone, PartialEq)]
-    struct Test2 {
-        pub id: u32,
+    #[test]
+    fn test_circular_index_overflow() {
+        let index = CircularIndex::new(usize::MAX - 2, usize::MAX);
+
+        assert_eq!(index + 1, usize::MAX - 1);
+        assert_eq!(index + 2, 0);
+        assert_eq!(index + 5, 3);
+        assert_eq!(index - (usize::MAX - 1), usize::MAX - 1);
+        assert_eq!(CircularIndex::new(1, usize::MAX) - 3, usize::MAX - 2);
+    }
+
+    #[test]
+    fn test_reader_pending() {
+        let reader = |last_index, generation| Reader {
+            generation,
+            last_index,
+            read_at: 0,
+            dropped_at: 0,
+            max_age: usize::MAX,
+        };
+        let last = CircularIndex::new(5, 8);
+
+        // Caught up
+        assert_eq!(reader(5, 3).pending(last, 3), 0);
+        // A whole lap behind
+        assert_eq!(reader(5, 2).pending(last, 3), 8);
+        assert_eq!(reader(2, 2).pending(last, 3), 3);
+        // Behind across the end of the buffer
+        assert_eq!(reader(6, 2).pending(CircularIndex::new(1, 8), 3), 3);
+        assert_eq!(reader(7, 2).pending(CircularIndex::new(0, 8), 3), 1);
+
+        let last = CircularIndex::new(0, usize::MAX);
+        assert_eq!(reader(usize::MAX - 1, 0).pending(last, 1), 1);
+        assert_eq!(reader(0, 0).pending(last, 1), usize::MAX);
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Zero-sized events don't need any memory
+        let mut buffer = RingBuffer::<()>::new(usize::MAX);
+        let mut reader = buffer.new_reader_id();
+
+        buffer.iter_write(vec![(); 3]);
+        assert_eq!(buffer.read(&mut reader).len(), 3);
+        buffer.single_write(());
+        assert_eq!(buffer.read(&mut reader).len(), 1);
+        assert_eq!(buffer.reader_lag(&reader), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_grow_overflow() {
+        let mut buffer = RingBuffer::<()>::new(usize::MAX / 2 + 1);
+        let _reader = buffer.new_reader_id();
+
+        buffer.iter_write(vec![(); 4]);
+        buffer.ensure_additional(usize::MAX / 2);
     }
 
     #[test]
@@ -667,6 +2548,49 @@ mod tests {
         assert_eq!(Vec::<Test>::default(), data.cloned().collect::<Vec<_>>())
     }
 
+    #[test]
+    fn test_read_full_lap() {
+        let mut buffer = RingBuffer::<Test>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+
+        // Writing exactly the capacity brings the write position back to
+        // where the reader is.
+        buffer.iter_write((0..4).map(|id| Test { id }));
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(
+            buffer.read(&mut reader_id).map(|t| t.id).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+
+        buffer.iter_write((4..5).map(|id| Test { id }));
+        buffer.read(&mut reader_id);
+        buffer.iter_write((5..9).map(|id| Test { id }));
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(
+            buffer.read(&mut reader_id).map(|t| t.id).collect::<Vec<_>>(),
+            vec![5, 6, 7, 8]
+        );
+        assert_eq!(buffer.read(&mut reader_id).len(), 0);
+    }
+
+    #[test]
+    fn test_read_caught_up() {
+        let mut buffer = RingBuffer::<Test>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        buffer.iter_write((0..3).map(|id| Test { id }));
+        assert_eq!(buffer.read(&mut reader_id).len(), 3);
+
+        for _ in 0..3 {
+            assert_eq!(buffer.read(&mut reader_id).len(), 0);
+        }
+        buffer.single_write(Test { id: 3 });
+        assert_eq!(
+            buffer.read(&mut reader_id).cloned().collect::<Vec<_>>(),
+            vec![Test { id: 3 }]
+        );
+        assert_eq!(buffer.stats().reads, 5);
+    }
+
     #[test]
     fn test_empty_read_write_before_id() {
         let mut buffer = RingBuffer::<Test>::new(10);
@@ -709,6 +2633,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_copy_write() {
+        let check = |policy, sizes: &[usize], read_every: usize| {
+            let mut copied = RingBuffer::<u32>::with_policy(4, policy);
+            let mut iterated = RingBuffer::<u32>::with_policy(4, policy);
+            let mut readers = (copied.new_reader_id(), iterated.new_reader_id());
+            let mut next = 0;
+
+            for (n, &size) in sizes.iter().enumerate() {
+                let data: Vec<u32> = (next..next + size as u32).collect();
+                next += size as u32;
+                copied.copy_write(&data);
+                iterated.iter_write(data);
+                if n % read_every == 0 {
+                    let read: Vec<_> = copied.read(&mut readers.0).collect();
+                    assert_eq!(read, iterated.read(&mut readers.1).collect::<Vec<_>>());
+                }
+                let stored: Vec<_> = copied.iter_all().collect();
+                assert_eq!(stored, iterated.iter_all().collect::<Vec<_>>());
+                assert_eq!(copied.written, iterated.written);
+                assert_eq!(copied.capacity(), iterated.capacity());
+            }
+        };
+
+        let sizes = [1, 3, 0, 5, 2, 9, 4, 1, 7];
+        check(DropPolicy::Grow, &sizes, 1);
+        check(DropPolicy::Grow, &sizes, 3);
+        check(DropPolicy::DropNewest, &sizes, 1);
+        check(DropPolicy::DropNewest, &sizes, 2);
+
+        // Without readers, only the newest elements are kept.
+        let mut buffer = RingBuffer::<u32>::new(4);
+        buffer.copy_write(&[0, 1, 2]);
+        buffer.copy_write(&[3, 4, 5, 6, 7, 8]);
+        assert_eq!(buffer.iter_all().cloned().collect::<Vec<_>>(), [5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the maximum age of a reader must not be zero")]
+    fn test_zero_max_age() {
+        let mut buffer = RingBuffer::<u32>::new(8);
+        buffer.new_reader_id_max_age(0);
+    }
+
+    #[test]
+    fn test_read_all_stale() {
+        let mut buffer = RingBuffer::<u32>::new(8);
+        let mut reader = buffer.new_reader_id();
+        // Only reachable by bypassing the check in `new_reader_id_max_age`
+        buffer.meta.reader_exclusive(reader.id).max_age = 0;
+
+        buffer.iter_write(0..3);
+        assert_eq!(buffer.read(&mut reader).len(), 0);
+        buffer.meta.reader_exclusive(reader.id).max_age = 1;
+        buffer.iter_write(3..5);
+        assert_eq!(buffer.read(&mut reader).cloned().collect::<Vec<_>>(), [4]);
+    }
+
     /// If you're getting a compilation error here this test has failed!
     #[test]
     fn test_send_sync() {
@@ -797,6 +2779,177 @@ mod tests {
         assert_eq!(None, data.next());
     }
 
+    #[test]
+    fn test_any_all() {
+        let mut buffer = RingBuffer::<Test>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        buffer.iter_write((0..3).map(|id| Test { id }));
+        buffer.read(&mut reader_id);
+        // Wraps around the end
+        buffer.iter_write((3..7).map(|id| Test { id }));
+
+        let mut iter = buffer.read(&mut reader_id);
+        assert!(iter.any(|t| t.id == 4));
+        assert_eq!(iter.len(), 2);
+        assert!(iter.all(|t| t.id > 4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(buffer.reader_lag(&reader_id), 0);
+
+        let mut iter = buffer.iter_all();
+        assert!(!iter.all(|t| t.id != 5));
+        assert_eq!(iter.next().map(|t| t.id), Some(6));
+        assert!(!buffer.iter_all().any(|t| t.id > 6));
+        assert!(buffer.iter_all().all(|t| t.id >= 3));
+        assert!(!buffer.iter_window(0, 0).any(|_| true));
+        assert!(buffer.iter_window(0, 0).all(|_| false));
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let mut source = RingBuffer::<Test>::new(4);
+        source.iter_write((0..6).map(|id| Test { id }));
+
+        let mut buffer = RingBuffer::<Test>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        buffer.iter_write((10..13).map(|id| Test { id }));
+        let allocation = buffer.data.data.as_ptr();
+
+        buffer.clone_from(&source);
+        assert_eq!(buffer.data.data.as_ptr(), allocation);
+        assert!(buffer.iter_all().eq(source.iter_all()));
+        assert_eq!(buffer.written(), 6);
+        assert_eq!(buffer.read(&mut reader_id).len(), 0);
+
+        buffer.single_write(Test { id: 6 });
+        assert_eq!(buffer.read(&mut reader_id).next(), Some(&Test { id: 6 }));
+        let clone = buffer.clone();
+        assert!(clone.iter_all().eq(buffer.iter_all()));
+        assert_eq!(clone.iter_all().len(), 4);
+    }
+
+    #[test]
+    fn test_read_rev() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+
+        // Make the data wrap around
+        buffer.iter_write(0..3);
+        buffer.read(&mut reader_id);
+        buffer.iter_write(3..7);
+
+        let mut iter = buffer.read(&mut reader_id);
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.rev().cloned().collect::<Vec<_>>(), vec![5, 4]);
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        buffer.iter_write(0..6);
+        let debug = format!("{:?}", buffer);
+        assert!(debug.contains("written: 6"), "{}", debug);
+        assert!(debug.contains("retained: [2, 3, 4, 5]"), "{}", debug);
+
+        let _reader_id = buffer.new_reader_id();
+        buffer.iter_write(6..20);
+        let debug = format!("{:?}", buffer);
+        assert!(debug.contains("retained: [4, 5, 6, 7, ..8 more, 16, 17, 18, 19]"), "{}", debug);
+    }
+
+    #[test]
+    fn test_iter_len_across_end() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        buffer.iter_write(0..3);
+        buffer.read(&mut reader_id);
+        // 3 is stored in the last slot, 4 and 5 in the first two
+        buffer.iter_write(3..6);
+
+        let mut iter = buffer.read(&mut reader_id);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert!(!iter.is_empty());
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!((iter.len(), iter.is_empty()), (0, true));
+        assert!(buffer.read(&mut reader_id).is_empty());
+    }
+
+    #[test]
+    fn test_read_fused() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        buffer.iter_write(0..3);
+        buffer.read(&mut reader_id);
+        buffer.iter_write(3..6);
+
+        let mut iter = buffer.read(&mut reader_id);
+        assert_eq!(iter.by_ref().count(), 3);
+        for _ in 0..3 {
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+            assert_eq!(iter.len(), 0);
+        }
+
+        let mut iter = buffer.iter_window(1, 1);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_maintain() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        let mut reader_id = buffer.new_reader_id();
+        let dropped = buffer.new_reader_id();
+
+        drop(dropped);
+        buffer.maintain();
+        assert!(!buffer.meta.reader_exclusive(1).active());
+        assert_eq!(buffer.meta.free, vec![1]);
+
+        for i in 0..10_000 {
+            buffer.iter_write(i..i + 3);
+            if i % 7 == 0 {
+                buffer.maintain();
+            }
+            assert_eq!(
+                buffer.read(&mut reader_id).cloned().collect::<Vec<_>>(),
+                vec![i, i + 1, i + 2]
+            );
+        }
+        assert_eq!(buffer.capacity(), 4);
+    }
+
+    #[test]
+    fn test_iter_window() {
+        let mut buffer = RingBuffer::<i32>::new(4);
+        assert_eq!(buffer.iter_all().len(), 0);
+
+        buffer.iter_write(0..2);
+        assert_eq!(buffer.iter_all().cloned().collect::<Vec<_>>(), vec![0, 1]);
+
+        // Stored data is now 4, 5, 6 | 3
+        buffer.iter_write(2..7);
+        assert_eq!(
+            buffer.iter_all().cloned().collect::<Vec<_>>(),
+            vec![3, 4, 5, 6]
+        );
+        assert_eq!(
+            buffer.iter_window(0, 2).cloned().collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(
+            buffer.iter_window(2, 5).cloned().collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+        assert_eq!(buffer.iter_window(5, 1).len(), 0);
+        assert_eq!(buffer.iter_window(1, 0).len(), 0);
+    }
+
     fn events(n: u32) -> Vec<Test> {
         (0..n).map(|i| Test { id: i }).collect::<Vec<_>>()
     }
@@ -0,0 +1,386 @@
+//! Sharing a channel between threads.
+
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use crate::{BufferHealth, Event, EventChannel, EventIterator, ReaderId};
+
+/// An `EventChannel` behind a mutex, so producers and consumers on different
+/// threads can share it, e.g. through an `Arc`.
+///
+/// ## Examples
+///
+/// ```
+/// use std::{sync::Arc, thread};
+///
+/// use shrev::{EventChannel, SyncEventChannel};
+///
+/// let channel = Arc::new(SyncEventChannel::new(EventChannel::new()));
+/// let mut reader = channel.register_reader();
+///
+/// let producer = {
+///     let channel = channel.clone();
+///     thread::spawn(move || channel.single_write(5))
+/// };
+/// producer.join().unwrap();
+///
+/// let events = channel.read_with(&mut reader, |events| events.cloned().collect::<Vec<_>>());
+/// assert_eq!(events, vec![5]);
+/// ```
+#[derive(Debug)]
+pub struct SyncEventChannel<E> {
+    channel: Mutex<EventChannel<E>>,
+    /// Notified after every write, see `read_timeout`.
+    written: Condvar,
+    /// Woken after every write, see `poll_read`.
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl<E> SyncEventChannel<E>
+where
+    E: Event,
+{
+    /// Wraps `channel` so it can be shared between threads.
+    pub fn new(channel: EventChannel<E>) -> Self {
+        SyncEventChannel {
+            channel: Mutex::new(channel),
+            written: Condvar::new(),
+            wakers: Mutex::new(vec![]),
+        }
+    }
+
+    /// Locks the channel, giving access to all of its methods.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if a thread panicked while holding the lock.
+    ///
+    /// Writes through the returned guard don't wake up threads waiting in
+    /// `read_timeout` or tasks waiting on `poll_read`; those only notice the
+    /// events once they are woken up by another write, or time out.
+    pub fn lock(&self) -> MutexGuard<'_, EventChannel<E>> {
+        self.channel.lock().expect("`SyncEventChannel` lock poisoned")
+    }
+
+    /// Unwraps the channel.
+    pub fn into_inner(self) -> EventChannel<E> {
+        self.channel
+            .into_inner()
+            .expect("`SyncEventChannel` lock poisoned")
+    }
+
+    /// Register a new reader.
+    ///
+    /// See `EventChannel::register_reader`.
+    pub fn register_reader(&self) -> ReaderId<E> {
+        self.lock().register_reader()
+    }
+
+    /// Write a single event into storage.
+    pub fn single_write(&self, event: E) {
+        self.lock().single_write(event);
+        self.notify();
+    }
+
+    /// Write an iterator of events into storage.
+    ///
+    /// The events are written at once, so readers see the events of
+    /// concurrent writes in one piece.
+    pub fn iter_write<I>(&self, iter: I)
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.lock().iter_write(iter);
+        self.notify();
+    }
+
+    /// Wakes up everyone waiting for a write.
+    fn notify(&self) {
+        self.written.notify_all();
+        // Taken out and unlocked first, so a waker may poll again right away.
+        let wakers = {
+            let mut wakers = self.wakers.lock().expect("`SyncEventChannel` lock poisoned");
+            std::mem::take(&mut *wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Write a single event into storage, waiting for the readers to make
+    /// room for it if the channel is full.
+    ///
+    /// The channel counts as full if writing would make it grow, because a
+    /// reader has as many events pending as it can hold. In that case, this
+    /// releases the lock and yields to other threads, up to `spin_limit`
+    /// times, checking whether a reader caught up in between. If none did,
+    /// the event is returned.
+    pub fn write_single_blocking(&self, event: E, spin_limit: usize) -> Result<(), E> {
+        for attempt in 0..=spin_limit {
+            let mut channel = self.lock();
+            if channel.health() != BufferHealth::Overflowing {
+                channel.single_write(event);
+                drop(channel);
+                self.notify();
+
+                return Ok(());
+            }
+            drop(channel);
+
+            if attempt < spin_limit {
+                thread::yield_now();
+            }
+        }
+
+        Err(event)
+    }
+
+    /// Read the events pending for `reader_id` and pass them to `f`.
+    ///
+    /// The channel stays locked while `f` runs, so `f` must not use this
+    /// `SyncEventChannel`.
+    pub fn read_with<F, R>(&self, reader_id: &mut ReaderId<E>, f: F) -> R
+    where
+        F: FnOnce(EventIterator<'_, E>) -> R,
+    {
+        f(self.lock().read(reader_id))
+    }
+
+    /// Read clones of the events pending for `reader_id`, waiting up to
+    /// `timeout` for an event to be written if there are none.
+    ///
+    /// Returns an empty `Vec` if no event was written in time. Only writes
+    /// through this `SyncEventChannel`, not through `lock`, end the wait
+    /// early.
+    pub fn read_timeout(&self, reader_id: &mut ReaderId<E>, timeout: Duration) -> Vec<E>
+    where
+        E: Clone,
+    {
+        let (channel, _) = self
+            .written
+            .wait_timeout_while(self.lock(), timeout, |channel| {
+                channel.reader_lag(reader_id) == 0
+            })
+            .expect("`SyncEventChannel` lock poisoned");
+
+        channel.read(reader_id).cloned().collect()
+    }
+
+    /// Read clones of the events pending for `reader_id`, parking the thread
+    /// until an event is written if there are none.
+    ///
+    /// With a `timeout`, this is the same as `read_timeout`. Without one, it
+    /// waits until an event arrives, so it never returns an empty `Vec`; make
+    /// sure that some thread will write through this `SyncEventChannel`.
+    pub fn read_blocking(&self, reader_id: &mut ReaderId<E>, timeout: Option<Duration>) -> Vec<E>
+    where
+        E: Clone,
+    {
+        if let Some(timeout) = timeout {
+            return self.read_timeout(reader_id, timeout);
+        }
+
+        let channel = self
+            .written
+            .wait_while(self.lock(), |channel| channel.reader_lag(reader_id) == 0)
+            .expect("`SyncEventChannel` lock poisoned");
+
+        channel.read(reader_id).cloned().collect()
+    }
+
+    /// Read clones of the events pending for `reader_id`, or arrange for the
+    /// current task to be woken up by the next write if there are none.
+    ///
+    /// This is the building block for reading from async code, e.g. by
+    /// implementing a `Stream` on top of it, or with
+    /// `std::future::poll_fn`. Like with `read_timeout`, only writes through
+    /// this `SyncEventChannel` wake up the task.
+    pub fn poll_read(&self, reader_id: &mut ReaderId<E>, cx: &mut Context<'_>) -> Poll<Vec<E>>
+    where
+        E: Clone,
+    {
+        let channel = self.lock();
+        if channel.reader_lag(reader_id) > 0 {
+            return Poll::Ready(channel.read(reader_id).cloned().collect());
+        }
+        self.register_waker(cx.waker());
+
+        Poll::Pending
+    }
+
+    /// Returns whether `reader_id` has events pending, registering `waker`
+    /// to be woken up by the next write if it hasn't.
+    pub(crate) fn poll_pending(&self, reader_id: &ReaderId<E>, waker: &Waker) -> bool {
+        let channel = self.lock();
+        let pending = channel.reader_lag(reader_id) > 0;
+        if !pending {
+            self.register_waker(waker);
+        }
+
+        pending
+    }
+
+    /// Must be called while the channel is locked, so a write can't slip in
+    /// between checking for pending events and the registration.
+    fn register_waker(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().expect("`SyncEventChannel` lock poisoned");
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn test_write_single_blocking_full() {
+        let channel = SyncEventChannel::new(EventChannel::with_capacity(2));
+        let mut reader = channel.register_reader();
+
+        assert_eq!(channel.write_single_blocking(0, 0), Ok(()));
+        assert_eq!(channel.write_single_blocking(1, 0), Ok(()));
+        assert_eq!(channel.write_single_blocking(2, 3), Err(2));
+
+        channel.read_with(&mut reader, |events| assert_eq!(events.len(), 2));
+        assert_eq!(channel.write_single_blocking(2, 0), Ok(()));
+        assert_eq!(channel.into_inner().iter_all().cloned().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn test_concurrent_writers() {
+        let channel = Arc::new(SyncEventChannel::new(EventChannel::with_capacity(4)));
+        let mut reader = channel.register_reader();
+
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let channel = channel.clone();
+                thread::spawn(move || {
+                    for n in 0..250 {
+                        match n % 2 {
+                            0 => channel.single_write((writer, n)),
+                            _ => channel.iter_write(vec![(writer, n)]),
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut received = vec![vec![]; 4];
+        while received.iter().map(Vec::len).sum::<usize>() < 1000 {
+            for (writer, n) in channel.read_timeout(&mut reader, Duration::from_secs(10)) {
+                received[writer].push(n);
+            }
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        // Each writer's events arrive in the order they were written
+        for events in received {
+            assert_eq!(events, (0..250).collect::<Vec<_>>());
+        }
+        assert_eq!(channel.lock().next_sequence(), 1000);
+    }
+
+    #[test]
+    fn test_poll_read() {
+        use std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            task::Wake,
+        };
+
+        struct CountWakes(AtomicUsize);
+
+        impl Wake for CountWakes {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let channel = SyncEventChannel::new(EventChannel::new());
+        let mut reader = channel.register_reader();
+        assert_eq!(channel.poll_read(&mut reader, &mut cx), Poll::Pending);
+        assert_eq!(channel.poll_read(&mut reader, &mut cx), Poll::Pending);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+
+        channel.iter_write(vec![1, 2]);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        channel.single_write(3);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        assert_eq!(channel.poll_read(&mut reader, &mut cx), Poll::Ready(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_read_timeout() {
+        let channel = Arc::new(SyncEventChannel::new(EventChannel::new()));
+        let mut reader = channel.register_reader();
+        let timeout = Duration::from_millis(20);
+        assert_eq!(channel.read_timeout(&mut reader, timeout), []);
+
+        channel.single_write(0);
+        assert_eq!(channel.read_timeout(&mut reader, timeout), [0]);
+
+        let producer = {
+            let channel = channel.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                channel.single_write(1);
+            })
+        };
+        let read = channel.read_timeout(&mut reader, Duration::from_secs(10));
+        assert_eq!(read, [1]);
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn test_read_blocking() {
+        let channel = Arc::new(SyncEventChannel::new(EventChannel::new()));
+        let mut reader = channel.register_reader();
+        let timeout = Some(Duration::from_millis(10));
+        assert_eq!(channel.read_blocking(&mut reader, timeout), []);
+
+        let producer = {
+            let channel = channel.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                channel.iter_write(vec![1, 2]);
+            })
+        };
+        assert_eq!(channel.read_blocking(&mut reader, None), [1, 2]);
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn test_write_single_blocking_unblocked() {
+        let channel = Arc::new(SyncEventChannel::new(EventChannel::with_capacity(2)));
+        let mut reader = channel.register_reader();
+        channel.single_write(0);
+        channel.single_write(1);
+
+        let producer = {
+            let channel = channel.clone();
+            thread::spawn(move || channel.write_single_blocking(2, usize::MAX))
+        };
+        thread::sleep(Duration::from_millis(10));
+        let read = channel.read_with(&mut reader, |events| events.cloned().collect::<Vec<_>>());
+        assert_eq!(read, [0, 1]);
+
+        assert_eq!(producer.join().unwrap(), Ok(()));
+        channel.read_with(&mut reader, |events| {
+            assert_eq!(events.cloned().collect::<Vec<_>>(), [2])
+        });
+    }
+}
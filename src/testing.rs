@@ -0,0 +1,43 @@
+//! Helpers for writing tests against an `EventChannel`.
+//!
+//! Only available with the `testing` feature.
+
+use std::fmt::Debug;
+
+use crate::{Event, EventChannel, ReaderId};
+
+/// Extension methods making tests of event channels more concise.
+pub trait EventChannelExt<E> {
+    /// Writes all events of `iter`, which doesn't need to know its length.
+    fn write_events<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = E>;
+
+    /// Reads with `reader_id` and asserts that exactly the `expected` events
+    /// are returned.
+    fn assert_reads(&self, reader_id: &mut ReaderId<E>, expected: &[E])
+    where
+        E: Debug + PartialEq;
+}
+
+impl<E> EventChannelExt<E> for EventChannel<E>
+where
+    E: Event,
+{
+    fn write_events<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = E>,
+    {
+        self.drain_vec_write(&mut iter.into_iter().collect());
+    }
+
+    #[track_caller]
+    fn assert_reads(&self, reader_id: &mut ReaderId<E>, expected: &[E])
+    where
+        E: Debug + PartialEq,
+    {
+        let events = self.read(reader_id).collect::<Vec<_>>();
+
+        assert_eq!(events, expected.iter().collect::<Vec<_>>());
+    }
+}
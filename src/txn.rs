@@ -0,0 +1,77 @@
+//! Batching writes so they're applied all at once.
+
+use crate::{Event, EventChannel};
+
+/// A batch of events which are written to a channel together.
+///
+/// Created with `EventChannel::begin`. Events pushed to the transaction are
+/// only written once it is committed; a transaction which gets rolled back
+/// or dropped doesn't write anything.
+#[derive(Debug)]
+pub struct WriteTxn<'a, E> {
+    channel: &'a mut EventChannel<E>,
+    events: Vec<E>,
+}
+
+impl<'a, E> WriteTxn<'a, E>
+where
+    E: Event,
+{
+    pub(crate) fn new(channel: &'a mut EventChannel<E>) -> Self {
+        WriteTxn {
+            channel,
+            events: vec![],
+        }
+    }
+
+    /// Add an event to the transaction.
+    pub fn push(&mut self, event: E) {
+        self.events.push(event);
+    }
+
+    /// Returns the events pushed so far.
+    pub fn events(&self) -> &[E] {
+        &self.events
+    }
+
+    /// Write all pushed events to the channel, in one write.
+    pub fn commit(mut self) {
+        self.channel.drain_vec_write(&mut self.events);
+    }
+
+    /// Discard all pushed events.
+    pub fn rollback(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_rollback() {
+        let mut channel = EventChannel::with_capacity(4);
+        let mut reader = channel.register_reader();
+
+        let mut txn = channel.begin();
+        txn.push(1);
+        txn.push(2);
+        txn.rollback();
+        assert_eq!(channel.reader_lag(&reader), 0);
+
+        let mut txn = channel.begin();
+        txn.push(3);
+        drop(txn);
+        assert_eq!(channel.reader_lag(&reader), 0);
+
+        let mut txn = channel.begin();
+        txn.push(4);
+        txn.push(5);
+        assert_eq!(txn.events(), &[4, 5]);
+        txn.commit();
+        assert_eq!(channel.stats().writes, 1);
+        assert_eq!(
+            channel.read(&mut reader).cloned().collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+    }
+}
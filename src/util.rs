@@ -1,8 +1,16 @@
 use std::{
     fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
     sync::Arc,
 };
 
+// The atomics shared between readers and the channel, which `loom` needs to
+// track when model checking.
+#[cfg(loom)]
+pub(crate) use loom::sync::{atomic::AtomicU64, Arc as SharedArc};
+#[cfg(not(loom))]
+pub(crate) use std::sync::{atomic::AtomicU64, Arc as SharedArc};
+
 /// A unique ID that can be used to assert two objects refer to another common
 /// object.
 ///
@@ -81,6 +89,12 @@ impl PartialEq for Reference {
     }
 }
 
+impl Hash for Reference {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_usize().hash(state);
+    }
+}
+
 /// A struct which implements `Sync` for non-`Sync` types by only allowing
 /// mutable access.
 ///
@@ -0,0 +1,47 @@
+use std::{sync::Arc, thread};
+
+use shrev::AtomicRingBuffer;
+
+const PRODUCERS: u32 = 4;
+const EVENTS: u32 = 10_000;
+
+#[test]
+fn multiple_producers() {
+    let buffer = Arc::new(AtomicRingBuffer::new(256));
+
+    let producers = (0..PRODUCERS)
+        .map(|producer| {
+            let buffer = buffer.clone();
+            thread::spawn(move || {
+                for n in 0..EVENTS {
+                    let mut event = (producer, n);
+                    while let Err(e) = buffer.single_write(event) {
+                        event = e;
+                        thread::yield_now();
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut next = vec![0; PRODUCERS as usize];
+    let mut read = 0;
+    while read < PRODUCERS * EVENTS {
+        match buffer.read_single() {
+            Some((producer, n)) => {
+                // Events of one producer arrive in order, without gaps or duplicates.
+                assert_eq!(next[producer as usize], n);
+                next[producer as usize] += 1;
+                read += 1;
+            }
+            None => thread::yield_now(),
+        }
+    }
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    assert_eq!(buffer.read_single(), None);
+    assert!(next.iter().all(|&n| n == EVENTS));
+}
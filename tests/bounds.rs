@@ -1,3 +1,5 @@
+use std::{cell::Cell, rc::Rc};
+
 use shrev::*;
 
 fn is_sync<T: Sync>() {}
@@ -7,6 +9,7 @@ fn is_send<T: Send>() {}
 fn event_channel_bounds() {
     is_send::<EventChannel<i32>>();
     is_sync::<EventChannel<i32>>();
+    is_send::<EventChannel<Cell<i32>>>();
 }
 
 #[test]
@@ -15,8 +18,66 @@ fn reader_id_bounds() {
     is_sync::<ReaderId<i32>>();
 }
 
+#[test]
+fn reader_id_bounds_independent_of_event() {
+    is_send::<ReaderId<Cell<i32>>>();
+    is_sync::<ReaderId<Cell<i32>>>();
+    is_send::<ReaderId<Rc<i32>>>();
+    is_sync::<ReaderId<Rc<i32>>>();
+}
+
 #[test]
 fn event_iterator_bounds() {
     is_send::<EventIterator<'static, i32>>();
     is_sync::<EventIterator<'static, i32>>();
 }
+
+#[test]
+fn cursor_bounds() {
+    is_send::<Cursor>();
+    is_sync::<Cursor>();
+}
+
+#[test]
+fn atomic_reader_id_bounds() {
+    is_send::<AtomicReaderId<Cell<i32>>>();
+    is_sync::<AtomicReaderId<Cell<i32>>>();
+}
+
+#[test]
+fn atomic_ring_buffer_bounds() {
+    is_send::<AtomicRingBuffer<i32>>();
+    is_sync::<AtomicRingBuffer<i32>>();
+}
+
+#[test]
+fn sync_event_channel_bounds() {
+    is_send::<SyncEventChannel<Cell<i32>>>();
+    is_sync::<SyncEventChannel<Cell<i32>>>();
+}
+
+#[test]
+fn spsc_bounds() {
+    is_send::<SpscWriter<Cell<i32>>>();
+    is_send::<SpscReader<Cell<i32>>>();
+}
+
+#[test]
+fn reader_group_bounds() {
+    is_send::<ReaderGroup<i32>>();
+    is_sync::<ReaderGroup<i32>>();
+}
+
+#[test]
+fn wrapper_channel_bounds() {
+    is_send::<FixedRingBuffer<i32, 4>>();
+    is_sync::<FixedRingBuffer<i32, 4>>();
+    is_send::<PriorityChannel<i32>>();
+    is_sync::<PriorityChannel<i32>>();
+    is_send::<StampedChannel<i32>>();
+    is_sync::<StampedChannel<i32>>();
+    is_send::<Recorder<i32>>();
+    is_sync::<Recorder<i32>>();
+    is_send::<PartitionedChannel<u32, i32>>();
+    is_sync::<PartitionedChannel<u32, i32>>();
+}
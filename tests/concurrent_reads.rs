@@ -0,0 +1,83 @@
+//! Reads from many threads at once, through shared references to one
+//! `EventChannel`.
+
+use std::thread;
+
+use shrev::EventChannel;
+
+const READERS: usize = 8;
+const ROUNDS: usize = 300;
+
+#[test]
+fn concurrent_reads() {
+    let mut channel = EventChannel::with_capacity(8);
+    let mut readers: Vec<_> = (0..READERS).map(|_| channel.register_reader()).collect();
+    let mut cursors = vec![channel.register_cursor(); READERS];
+    let mut received = vec![vec![]; READERS];
+    let mut written = 0;
+
+    for round in 0..ROUNDS {
+        let num = round % 7;
+        channel.iter_write(written..written + num);
+        written += num;
+
+        let channel = &channel;
+        thread::scope(|scope| {
+            let threads = readers.iter_mut().zip(&mut cursors).zip(&mut received);
+            for (index, ((reader, cursor), received)) in threads.enumerate() {
+                scope.spawn(move || {
+                    // Every reader keeps up at its own pace.
+                    match (round + index) % 3 {
+                        0 => received.extend(channel.read(reader).cloned()),
+                        1 => {
+                            let peeked: Vec<_> = channel.peek(reader).cloned().collect();
+                            let acked = channel.ack(reader, index);
+                            received.extend_from_slice(&peeked[..acked]);
+                        }
+                        _ => assert!(channel.reader_lag(reader) <= written),
+                    }
+
+                    assert_eq!(channel.read_cursor(cursor).count(), num);
+                });
+            }
+        });
+    }
+
+    for (reader, received) in readers.iter_mut().zip(&mut received) {
+        received.extend(channel.read(reader).cloned());
+        assert_eq!(*received, (0..written).collect::<Vec<_>>());
+    }
+    assert_eq!(channel.stats().events_written, written as u64);
+}
+
+#[test]
+fn shared_atomic_reader() {
+    let mut channel = EventChannel::with_capacity(8);
+    let reader = channel.register_atomic_reader();
+    let mut written = 0;
+    let mut received = vec![];
+
+    for round in 0..ROUNDS {
+        let num = round % 11;
+        channel.iter_write(written..written + num);
+        written += num;
+
+        let (channel, reader) = (&channel, &reader);
+        let reads: Vec<Vec<usize>> = thread::scope(|scope| {
+            let threads: Vec<_> = (0..READERS)
+                .map(|_| scope.spawn(move || channel.read_atomic(reader).cloned().collect()))
+                .collect();
+
+            threads.into_iter().map(|t| t.join().unwrap()).collect()
+        });
+        // Every event is read by exactly one of the threads, in order.
+        for read in &reads {
+            assert!(read.windows(2).all(|w| w[0] + 1 == w[1]));
+        }
+        received.extend(reads.into_iter().flatten());
+    }
+
+    received.sort();
+    assert_eq!(received, (0..written).collect::<Vec<_>>());
+    assert_eq!(channel.atomic_reader_lag(&reader), 0);
+}
@@ -0,0 +1,105 @@
+//! Model checks the positions of `AtomicReaderId`s, which are read and
+//! swapped through shared references while the channel syncs them.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`.
+
+#![cfg(loom)]
+
+use loom::{
+    sync::{Arc, RwLock},
+    thread,
+};
+
+use shrev::{AtomicReaderId, EventChannel};
+
+type Shared = Arc<(RwLock<EventChannel<u32>>, AtomicReaderId<u32>)>;
+
+fn shared(size: usize) -> Shared {
+    let mut channel = EventChannel::with_capacity(size);
+    let reader = channel.register_atomic_reader();
+
+    Arc::new((RwLock::new(channel), reader))
+}
+
+fn read(shared: &Shared) -> Vec<u32> {
+    let (channel, reader) = &**shared;
+
+    channel.read().unwrap().read_atomic(reader).cloned().collect()
+}
+
+#[test]
+fn read_while_write() {
+    loom::model(|| {
+        let shared = shared(2);
+
+        let readers: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || read(&shared))
+            })
+            .collect();
+        for n in 0..3 {
+            shared.0.write().unwrap().single_write(n);
+        }
+
+        let mut received: Vec<_> = readers
+            .into_iter()
+            .flat_map(|reader| reader.join().unwrap())
+            .collect();
+        received.extend(read(&shared));
+        // Every event is read exactly once, none is overwritten.
+        received.sort();
+        assert_eq!(received, [0, 1, 2]);
+    });
+}
+
+#[test]
+fn position_synced_on_grow() {
+    loom::model(|| {
+        let shared = shared(2);
+        shared.0.write().unwrap().iter_write(0..2);
+
+        let reader = {
+            let shared = shared.clone();
+            thread::spawn(move || read(&shared))
+        };
+        // Needs to grow unless the read above has happened already, which
+        // the channel only notices by syncing the position.
+        shared.0.write().unwrap().iter_write(2..4);
+
+        let read = reader.join().unwrap();
+        let capacity = shared.0.read().unwrap().capacity();
+        match read.len() {
+            2 => assert_eq!((read, capacity), (vec![0, 1], 2)),
+            _ => assert_eq!((read, capacity), (vec![0, 1, 2, 3], 4)),
+        }
+    });
+}
+
+#[test]
+fn batches_claimed_once() {
+    loom::model(|| {
+        let shared = shared(4);
+        shared.0.write().unwrap().iter_write(0..3);
+
+        let members: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    let (channel, reader) = &*shared;
+                    let channel = channel.read().unwrap();
+
+                    channel.read_atomic_batch(reader, 2).cloned().collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut received: Vec<_> = members
+            .into_iter()
+            .flat_map(|member| member.join().unwrap())
+            .collect();
+        received.extend(read(&shared));
+        received.sort();
+        assert_eq!(received, [0, 1, 2]);
+    });
+}
@@ -0,0 +1,168 @@
+//! Compares random sequences of operations on an `EventChannel` against a
+//! simple model, where every reader has a queue of the events it should
+//! receive.
+
+use std::collections::VecDeque;
+
+use shrev::{EventChannel, ReaderId};
+
+/// Linear congruential generator, so runs are reproducible from their seed.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+
+        self.0 >> 33
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+struct Model {
+    readers: Vec<(ReaderId<u64>, VecDeque<u64>)>,
+    next_event: u64,
+}
+
+impl Model {
+    fn write(&mut self, num: u64) -> Vec<u64> {
+        let events: Vec<_> = (self.next_event..self.next_event + num).collect();
+        self.next_event += num;
+        for (_, pending) in &mut self.readers {
+            pending.extend(&events);
+        }
+
+        events
+    }
+}
+
+fn simulate(seed: u64, steps: usize) {
+    let mut rng = Lcg(seed);
+    let mut channel = EventChannel::with_capacity(2 + rng.below(8) as usize);
+    let mut model = Model {
+        readers: vec![],
+        next_event: 0,
+    };
+
+    for step in 0..steps {
+        let context = format!("seed {}, step {}", seed, step);
+
+        match rng.below(10) {
+            0..=2 => {
+                let events = model.write(rng.below(12));
+                channel.iter_write(events);
+            }
+            3 => {
+                let events = model.write(1);
+                channel.single_write(events[0]);
+            }
+            4 => {
+                let reader = channel.register_reader();
+                model.readers.push((reader, VecDeque::new()));
+            }
+            5 if !model.readers.is_empty() => {
+                let index = rng.below(model.readers.len() as u64) as usize;
+                let (reader, pending) = &model.readers[index];
+                let duplicate = channel.duplicate_reader(reader);
+                let pending = pending.clone();
+                model.readers.push((duplicate, pending));
+            }
+            6 if !model.readers.is_empty() => {
+                let index = rng.below(model.readers.len() as u64) as usize;
+                model.readers.swap_remove(index);
+                if rng.below(2) == 0 {
+                    channel.maintain();
+                }
+            }
+            7 if !model.readers.is_empty() => {
+                let index = rng.below(model.readers.len() as u64) as usize;
+                let (reader, pending) = &mut model.readers[index];
+                let peeked: Vec<_> = channel.peek(reader).cloned().collect();
+                assert_eq!(peeked, Vec::from(pending.clone()), "{}", context);
+
+                let acked = channel.ack(reader, rng.below(8) as usize);
+                pending.drain(..acked);
+            }
+            8 => match rng.below(5) {
+                0 => channel.compact(),
+                1 => channel.shrink_to(rng.below(8) as usize),
+                2 => channel.resize(rng.below(24) as usize),
+                3 => {
+                    channel.clear_consumed();
+                }
+                _ => {
+                    // Events are numbered like their sequence numbers
+                    let sequence = model.next_event.saturating_sub(rng.below(16));
+                    channel.expire_before(sequence);
+                    for (_, pending) in &mut model.readers {
+                        pending.retain(|&event| event >= sequence);
+                    }
+                }
+            },
+            _ if !model.readers.is_empty() => {
+                let index = rng.below(model.readers.len() as u64) as usize;
+                let (reader, pending) = &mut model.readers[index];
+                let read: Vec<_> = channel.read(reader).cloned().collect();
+                assert_eq!(read, Vec::from(pending.clone()), "{}", context);
+                pending.clear();
+            }
+            _ => {}
+        }
+
+        for (reader, pending) in &model.readers {
+            assert_eq!(channel.reader_lag(reader), pending.len(), "{}", context);
+        }
+    }
+
+    for (mut reader, pending) in model.readers.drain(..) {
+        let read: Vec<_> = channel.read(&mut reader).cloned().collect();
+        assert_eq!(read, Vec::from(pending), "seed {}, final read", seed);
+    }
+}
+
+/// Reads often enough for the channel to never grow, so the events wrap
+/// around the end of the ring buffer many times, at every possible offset.
+#[test]
+fn fifo_across_wraps() {
+    let mut rng = Lcg(7);
+    let mut channel = EventChannel::with_capacity(8);
+    let mut reader = channel.register_reader();
+    let mut expected = VecDeque::new();
+    let mut next_event = 0;
+
+    for step in 0..5_000 {
+        match rng.below(3) {
+            0 | 1 => {
+                let free = 8 - expected.len() as u64;
+                let events: Vec<u64> = (next_event..next_event + rng.below(free + 1)).collect();
+                next_event += events.len() as u64;
+                expected.extend(&events);
+                channel.iter_write(events);
+            }
+            _ => {
+                let read: Vec<_> = channel.read(&mut reader).cloned().collect();
+                assert_eq!(read, Vec::from(expected.clone()), "step {}", step);
+                expected.clear();
+            }
+        }
+        assert_eq!(channel.iter_all().len(), 8.min(next_event as usize), "step {}", step);
+    }
+    assert!(channel.next_sequence() > 1_000);
+}
+
+#[test]
+fn random_operations() {
+    for seed in 0..64 {
+        simulate(seed, 500);
+    }
+}
+
+#[test]
+fn long_run() {
+    simulate(0x5eed, 20_000);
+}